@@ -0,0 +1,641 @@
+use std::{
+    collections::{BTreeMap, HashMap, VecDeque},
+    env, fs,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
+};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph, Row, Table, TableState, Wrap},
+    DefaultTerminal, Frame,
+};
+
+// A full screen dashboard for the whole calendar: `aoc dash`.
+// The days are whatever lives in src/bin, every day is built and run as its own binary
+// with AOC_INPUT pointing at the sample or the real input, and the answers it prints
+// are checked against input/answers.txt. The last run of every day is kept in
+// target/dash/, so the dashboard shows the old answers and timings when it starts.
+
+const ANSWERS: &str = "input/answers.txt";
+// SAMPLE_ARGS are passed to days whose sample asks a different question than the real input
+const SAMPLE_ARGS: &[(&str, &[&str])] = &[("day15", &["--row", "10", "--size", "20"])];
+const KEYS: &str =
+    "↑/↓ select  enter run  r run all  tab sample/real  v visualise  a accept answers  q quit";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum Input {
+    Sample,
+    Real,
+}
+
+impl Input {
+    fn name(self) -> &'static str {
+        match self {
+            Input::Sample => "sample",
+            Input::Real => "real",
+        }
+    }
+
+    // path follows the layout of input/, like input/day13-test.txt and input/day13.txt
+    fn path(self, day: &Day) -> PathBuf {
+        match self {
+            Input::Sample => Path::new("input").join(format!("{}-test.txt", day.input)),
+            Input::Real => Path::new("input").join(format!("{}.txt", day.input)),
+        }
+    }
+
+    fn args(self, day: &Day) -> &'static [&'static str] {
+        match self {
+            Input::Sample => SAMPLE_ARGS
+                .iter()
+                .find(|(name, _)| *name == day.name)
+                .map_or(&[], |(_, args)| *args),
+            Input::Real => &[],
+        }
+    }
+}
+
+struct Day {
+    name: String,
+    // the input a binary reads, day16-p1 and day16-p2 both read day16
+    input: String,
+    vis: bool,
+}
+
+// discover finds the days in src/bin, both as directories and as single files
+fn discover() -> Result<Vec<Day>, String> {
+    let entries = fs::read_dir(Path::new("src").join("bin")).map_err(|e| {
+        format!(
+            "unable to read src/bin, run this from the repository: {}",
+            e
+        )
+    })?;
+
+    let mut days: Vec<Day> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let path = e.path();
+            let name = path.file_stem()?.to_str()?.to_string();
+            if !name.starts_with("day") {
+                return None;
+            }
+            let input = name.split("-p").next().unwrap_or(&name).to_string();
+            let vis = path.join("vis.rs").exists();
+            Some(Day { name, input, vis })
+        })
+        .collect();
+    days.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(days)
+}
+
+// Run is what came out of the last run of a day
+#[derive(Debug, Clone)]
+struct Run {
+    output: String,
+    time: Duration,
+    error: Option<String>,
+}
+
+impl Run {
+    // answers finds the `Part 1 - 123` lines in the output, a `Part 2:` line followed by
+    // a drawing like day 10's is kept as one answer with the rows separated by spaces
+    fn answers(&self) -> BTreeMap<u32, String> {
+        let mut answers = BTreeMap::new();
+        let mut lines = self.output.lines().peekable();
+
+        while let Some(line) = lines.next() {
+            let Some(rest) = line.strip_prefix("Part ") else {
+                continue;
+            };
+            let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+            let Ok(part) = digits.parse() else {
+                continue;
+            };
+            let rest = &rest[digits.len()..];
+
+            if let Some(answer) = rest.strip_prefix(" - ") {
+                // some days print their timing after the answer, like `473 (1.5ms)`
+                let answer = answer.split(" (").next().unwrap_or(answer);
+                answers.insert(part, answer.trim().to_string());
+            } else if rest.starts_with(':') {
+                let mut rows = Vec::new();
+                while let Some(row) = lines.next_if(|l| is_drawing(l)) {
+                    rows.push(row);
+                }
+                answers.insert(part, rows.join(" "));
+            }
+        }
+
+        answers
+    }
+
+    fn file(day: &Day, input: Input) -> PathBuf {
+        Path::new("target")
+            .join("dash")
+            .join(format!("{}-{}.txt", day.name, input.name()))
+    }
+
+    // save keeps the run around as `<micros> <error>` followed by the output
+    fn save(&self, day: &Day, input: Input) {
+        let file = Run::file(day, input);
+        let header = format!(
+            "{} {}",
+            self.time.as_micros(),
+            self.error.as_deref().unwrap_or("")
+        );
+        if let Some(dir) = file.parent() {
+            let _ = fs::create_dir_all(dir);
+        }
+        let _ = fs::write(file, format!("{}\n{}", header.trim_end(), self.output));
+    }
+
+    fn load(day: &Day, input: Input) -> Option<Run> {
+        let contents = fs::read_to_string(Run::file(day, input)).ok()?;
+        let (header, output) = contents.split_once('\n').unwrap_or((&contents, ""));
+        let (micros, error) = header.split_once(' ').unwrap_or((header, ""));
+
+        Some(Run {
+            output: output.to_string(),
+            time: Duration::from_micros(micros.parse().ok()?),
+            error: (!error.is_empty()).then(|| error.to_string()),
+        })
+    }
+}
+
+fn is_drawing(line: &str) -> bool {
+    !line.is_empty() && line.chars().all(|c| "█·#.".contains(c))
+}
+
+// run_day builds a day and then runs the binary directly, so the timing doesn't include cargo
+fn run_day(day: &str, input: &Path, args: &[&str]) -> Run {
+    let failed = |output: String, error: &str| Run {
+        output,
+        time: Duration::ZERO,
+        error: Some(error.to_string()),
+    };
+
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let build = Command::new(cargo)
+        .args(["build", "-q", "--release", "--bin", day])
+        .stdin(Stdio::null())
+        .output();
+    match build {
+        Ok(build) if build.status.success() => {}
+        Ok(build) => {
+            return failed(
+                String::from_utf8_lossy(&build.stderr).to_string(),
+                "build failed",
+            )
+        }
+        Err(e) => return failed(e.to_string(), "unable to start cargo"),
+    }
+
+    let started = Instant::now();
+    let result = Command::new(Path::new("target").join("release").join(day))
+        .args(args)
+        .env("AOC_INPUT", input)
+        .stdin(Stdio::null())
+        .output();
+    let time = started.elapsed();
+
+    match result {
+        Ok(result) => {
+            let mut output = String::from_utf8_lossy(&result.stdout).to_string();
+            output.push_str(&String::from_utf8_lossy(&result.stderr));
+            let error =
+                (!result.status.success()).then(|| format!("exited with {}", result.status));
+            Run {
+                output,
+                time,
+                error,
+            }
+        }
+        Err(e) => failed(e.to_string(), "unable to start"),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Status {
+    NoInput,
+    NotRun,
+    Running,
+    Solved,
+    Verified,
+    Failing(String),
+}
+
+impl Status {
+    fn style(&self) -> Style {
+        match self {
+            Status::NoInput | Status::NotRun => Style::new().fg(Color::DarkGray),
+            Status::Running => Style::new().fg(Color::Yellow),
+            Status::Solved => Style::new().fg(Color::Cyan),
+            Status::Verified => Style::new().fg(Color::Green),
+            Status::Failing(_) => Style::new().fg(Color::Red),
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Status::NoInput => "no input",
+            Status::NotRun => "not run",
+            Status::Running => "running",
+            Status::Solved => "solved",
+            Status::Verified => "verified",
+            Status::Failing(_) => "failing",
+        }
+    }
+}
+
+// Answers are the known good answers, one `<day> <sample|real> <part> <answer>` per line
+struct Answers {
+    known: HashMap<(String, Input), BTreeMap<u32, String>>,
+}
+
+impl Answers {
+    fn load() -> Answers {
+        let mut known: HashMap<(String, Input), BTreeMap<u32, String>> = HashMap::new();
+
+        let contents = fs::read_to_string(ANSWERS).unwrap_or_default();
+        for line in contents.lines() {
+            let mut fields = line.splitn(4, ' ');
+            let (Some(day), Some(input), Some(part), Some(answer)) =
+                (fields.next(), fields.next(), fields.next(), fields.next())
+            else {
+                continue;
+            };
+            let input = match input {
+                "sample" => Input::Sample,
+                "real" => Input::Real,
+                _ => continue,
+            };
+            let Ok(part) = part.parse() else {
+                continue;
+            };
+            known
+                .entry((day.to_string(), input))
+                .or_default()
+                .insert(part, answer.to_string());
+        }
+
+        Answers { known }
+    }
+
+    fn get(&self, day: &Day, input: Input) -> Option<&BTreeMap<u32, String>> {
+        self.known.get(&(day.name.clone(), input))
+    }
+
+    // accept replaces the known answers of a day and writes all of them back, sorted
+    fn accept(&mut self, day: &Day, input: Input, answers: BTreeMap<u32, String>) {
+        self.known.insert((day.name.clone(), input), answers);
+
+        let mut lines: Vec<String> = self
+            .known
+            .iter()
+            .flat_map(|((day, input), answers)| {
+                answers.iter().map(move |(part, answer)| {
+                    format!("{} {} {} {}", day, input.name(), part, answer)
+                })
+            })
+            .collect();
+        lines.sort();
+        let _ = fs::write(ANSWERS, lines.join("\n") + "\n");
+    }
+}
+
+struct Dashboard {
+    days: Vec<Day>,
+    runs: HashMap<(usize, Input), Run>,
+    answers: Answers,
+    input: Input,
+    table: TableState,
+    // days waiting for the worker, and the one it is running
+    queue: VecDeque<(usize, Input)>,
+    running: Option<(usize, Input)>,
+    message: String,
+}
+
+impl Dashboard {
+    fn new(days: Vec<Day>) -> Dashboard {
+        let mut runs = HashMap::new();
+        for (i, day) in days.iter().enumerate() {
+            for input in [Input::Sample, Input::Real] {
+                if let Some(run) = Run::load(day, input) {
+                    runs.insert((i, input), run);
+                }
+            }
+        }
+
+        Dashboard {
+            days,
+            runs,
+            answers: Answers::load(),
+            input: Input::Real,
+            table: TableState::default().with_selected(0),
+            queue: VecDeque::new(),
+            running: None,
+            message: String::new(),
+        }
+    }
+
+    fn selected(&self) -> usize {
+        self.table.selected().unwrap_or(0)
+    }
+
+    fn status(&self, i: usize) -> Status {
+        let day = &self.days[i];
+        if self.running == Some((i, self.input)) {
+            return Status::Running;
+        }
+        if !self.input.path(day).exists() {
+            return Status::NoInput;
+        }
+        let Some(run) = self.runs.get(&(i, self.input)) else {
+            return Status::NotRun;
+        };
+        if let Some(error) = &run.error {
+            return Status::Failing(error.clone());
+        }
+
+        let answers = run.answers();
+        if answers.is_empty() {
+            return Status::Failing("no answers in the output".to_string());
+        }
+        match self.answers.get(day, self.input) {
+            None => Status::Solved,
+            Some(known) => {
+                for (part, expected) in known {
+                    match answers.get(part) {
+                        Some(answer) if answer == expected => {}
+                        Some(answer) => {
+                            return Status::Failing(format!(
+                                "part {} is {}, expected {}",
+                                part, answer, expected
+                            ))
+                        }
+                        None => return Status::Failing(format!("part {} is missing", part)),
+                    }
+                }
+                Status::Verified
+            }
+        }
+    }
+
+    fn enqueue(&mut self, i: usize) {
+        if !self.input.path(&self.days[i]).exists() {
+            self.message = format!("{} has no {} input", self.days[i].name, self.input.name());
+        } else if !self.queue.contains(&(i, self.input)) {
+            self.queue.push_back((i, self.input));
+        }
+    }
+
+    // next_job hands the next queued day to the worker once it is idle
+    fn next_job(&mut self, jobs: &Sender<Job>) {
+        if self.running.is_some() {
+            return;
+        }
+        if let Some((i, input)) = self.queue.pop_front() {
+            let day = &self.days[i];
+            self.running = Some((i, input));
+            self.message = format!("running {} on the {} input", day.name, input.name());
+            let _ = jobs.send((day.name.clone(), input.path(day), input.args(day)));
+        }
+    }
+
+    fn finish(&mut self, run: Run) {
+        if let Some((i, input)) = self.running.take() {
+            run.save(&self.days[i], input);
+            self.runs.insert((i, input), run);
+            self.message = format!("{} finished", self.days[i].name);
+        }
+    }
+
+    fn accept(&mut self) {
+        let i = self.selected();
+        let Some(run) = self.runs.get(&(i, self.input)) else {
+            self.message = "run the day before accepting its answers".to_string();
+            return;
+        };
+        let answers = run.answers();
+        if run.error.is_some() || answers.is_empty() {
+            self.message = "only the answers of a successful run can be accepted".to_string();
+            return;
+        }
+
+        self.answers.accept(&self.days[i], self.input, answers);
+        self.message = format!(
+            "saved the {} answers of {} to {}",
+            self.input.name(),
+            self.days[i].name,
+            ANSWERS
+        );
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [title, table, output, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(self.days.len() as u16 + 3),
+            Constraint::Min(3),
+            Constraint::Length(2),
+        ])
+        .areas(frame.area());
+
+        let mut modes = vec![Span::styled(
+            "Advent of Code 2022  ",
+            Style::new().add_modifier(Modifier::BOLD),
+        )];
+        for input in [Input::Sample, Input::Real] {
+            let style = if input == self.input {
+                Style::new().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::new().fg(Color::DarkGray)
+            };
+            modes.push(Span::styled(format!(" {} ", input.name()), style));
+        }
+        frame.render_widget(Line::from(modes), title);
+
+        let rows: Vec<Row> = (0..self.days.len())
+            .map(|i| {
+                let status = self.status(i);
+                let run = self.runs.get(&(i, self.input));
+                let answers = run.map(|r| r.answers()).unwrap_or_default();
+                let answer = |part| answers.get(&part).map_or(String::new(), |a| shorten(a, 20));
+                let time = run
+                    .filter(|r| r.error.is_none())
+                    .map_or(String::new(), |r| format!("{:.1?}", r.time));
+
+                Row::new(vec![
+                    self.days[i].name.clone(),
+                    status.label().to_string(),
+                    answer(1),
+                    answer(2),
+                    time,
+                ])
+                .style(status.style())
+            })
+            .collect();
+        let widths = [
+            Constraint::Length(10),
+            Constraint::Length(10),
+            Constraint::Length(22),
+            Constraint::Length(22),
+            Constraint::Length(12),
+        ];
+        let header = Row::new(["day", "status", "part 1", "part 2", "time"])
+            .style(Style::new().add_modifier(Modifier::BOLD));
+        let days = Table::new(rows, widths)
+            .header(header)
+            .block(Block::bordered())
+            .row_highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(days, table, &mut self.table);
+
+        let i = self.selected();
+        let mut text = String::new();
+        if let Status::Failing(reason) = self.status(i) {
+            text.push_str(&format!("failing: {}\n\n", reason));
+        }
+        match self.runs.get(&(i, self.input)) {
+            Some(run) => text.push_str(&run.output),
+            None => text.push_str("not run yet"),
+        }
+        let title = format!(" {} ({} input) ", self.days[i].name, self.input.name());
+        frame.render_widget(
+            Paragraph::new(text)
+                .block(Block::bordered().title(title))
+                .wrap(Wrap { trim: false }),
+            output,
+        );
+
+        frame.render_widget(
+            Paragraph::new(vec![
+                Line::from(self.message.as_str()),
+                Line::styled(KEYS, Style::new().fg(Color::DarkGray)),
+            ]),
+            footer,
+        );
+    }
+}
+
+fn shorten(answer: &str, width: usize) -> String {
+    if answer.chars().count() <= width {
+        return answer.to_string();
+    }
+    let mut short: String = answer.chars().take(width - 1).collect();
+    short.push('…');
+    short
+}
+
+// Job is a day to run, with its input and arguments
+type Job = (String, PathBuf, &'static [&'static str]);
+
+// worker runs one day at a time in the background, so the screen keeps responding
+fn worker() -> (Sender<Job>, Receiver<Run>) {
+    let (jobs, job_receiver) = mpsc::channel::<Job>();
+    let (results, result_receiver) = mpsc::channel();
+
+    thread::spawn(move || {
+        for (day, input, args) in job_receiver {
+            if results.send(run_day(&day, &input, args)).is_err() {
+                return;
+            }
+        }
+    });
+
+    (jobs, result_receiver)
+}
+
+pub fn run() -> Result<(), String> {
+    let days = discover()?;
+    if days.is_empty() {
+        return Err("no days found in src/bin".to_string());
+    }
+
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, Dashboard::new(days));
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, mut dashboard: Dashboard) -> Result<(), String> {
+    let (jobs, results) = worker();
+
+    loop {
+        dashboard.next_job(&jobs);
+        if let Ok(run) = results.try_recv() {
+            dashboard.finish(run);
+        }
+
+        terminal
+            .draw(|frame| dashboard.draw(frame))
+            .map_err(|e| e.to_string())?;
+
+        if !event::poll(Duration::from_millis(100)).map_err(|e| e.to_string())? {
+            continue;
+        }
+        let Event::Key(key) = event::read().map_err(|e| e.to_string())? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        let selected = dashboard.selected();
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Up | KeyCode::Char('k') => dashboard.table.select_previous(),
+            KeyCode::Down | KeyCode::Char('j') if selected + 1 < dashboard.days.len() => {
+                dashboard.table.select_next()
+            }
+            KeyCode::Enter => dashboard.enqueue(selected),
+            KeyCode::Char('r') => {
+                for i in 0..dashboard.days.len() {
+                    if dashboard.input.path(&dashboard.days[i]).exists() {
+                        dashboard.enqueue(i);
+                    }
+                }
+            }
+            KeyCode::Tab | KeyCode::Char('s') => {
+                dashboard.input = match dashboard.input {
+                    Input::Sample => Input::Real,
+                    Input::Real => Input::Sample,
+                };
+            }
+            KeyCode::Char('a') => dashboard.accept(),
+            KeyCode::Char('v') => {
+                ratatui::restore();
+                dashboard.message = visualise(&dashboard.days[selected], dashboard.input);
+                *terminal = ratatui::init();
+            }
+            _ => {}
+        }
+    }
+}
+
+// visualise hands the terminal to a day's `vis` mode until it exits
+fn visualise(day: &Day, input: Input) -> String {
+    if !day.vis {
+        return format!(
+            "{} has no visualisation, add src/bin/{}/vis.rs for one",
+            day.name, day.name
+        );
+    }
+
+    let cargo = env::var("CARGO").unwrap_or_else(|_| "cargo".to_string());
+    let status = Command::new(cargo)
+        .args(["run", "-q", "--release", "--bin", &day.name, "--", "vis"])
+        .env("AOC_INPUT", input.path(day))
+        .status();
+    match status {
+        Ok(status) if status.success() => format!("closed the {} visualisation", day.name),
+        Ok(status) => format!("{} visualisation exited with {}", day.name, status),
+        Err(e) => format!("unable to start {}: {}", day.name, e),
+    }
+}
@@ -0,0 +1,1088 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use advent2022::rng::Rng;
+
+// Generator emits a random but valid puzzle input for one day.
+// Every generator is deterministic for a given seed and set of knobs,
+// and the output has no trailing newline, just like the real inputs.
+// Knobs that don't fit together, like more stacks than crates, are an error
+pub struct Generator {
+    pub day: &'static str,
+    pub knobs: &'static [Knob],
+    pub generate: fn(&mut Rng, &Knobs) -> Result<String, String>,
+}
+
+pub struct Knob {
+    pub name: &'static str,
+    pub default: i64,
+    pub min: i64,
+    pub max: i64,
+    pub help: &'static str,
+}
+
+const fn knob(name: &'static str, default: i64, min: i64, help: &'static str) -> Knob {
+    Knob {
+        name,
+        default,
+        min,
+        max: i64::MAX,
+        help,
+    }
+}
+
+impl Knob {
+    const fn up_to(self, max: i64) -> Knob {
+        Knob { max, ..self }
+    }
+
+    // range describes the values the knob accepts, for errors and --help
+    pub fn range(&self) -> String {
+        match self.max {
+            i64::MAX => format!("at least {}", self.min),
+            max => format!("{} to {}", self.min, max),
+        }
+    }
+}
+
+// Knobs holds the defaults of a generator, overridden by whatever was passed on the command line
+pub struct Knobs {
+    values: HashMap<&'static str, i64>,
+}
+
+impl Knobs {
+    pub fn new(generator: &Generator, overrides: &[(String, i64)]) -> Result<Knobs, String> {
+        let mut values: HashMap<&'static str, i64> = generator
+            .knobs
+            .iter()
+            .map(|k| (k.name, k.default))
+            .collect();
+
+        for (name, value) in overrides {
+            let knob = generator
+                .knobs
+                .iter()
+                .find(|k| k.name == name)
+                .ok_or_else(|| format!("{} has no knob named `{}`", generator.day, name))?;
+            if !(knob.min..=knob.max).contains(value) {
+                return Err(format!(
+                    "--{} has to be {}, not {}",
+                    name,
+                    knob.range(),
+                    value
+                ));
+            }
+            values.insert(knob.name, *value);
+        }
+
+        Ok(Knobs { values })
+    }
+
+    fn get(&self, name: &str) -> i64 {
+        *self
+            .values
+            .get(name)
+            .expect("generator asked for undeclared knob")
+    }
+
+    // count is get for knobs that count things, which are never negative
+    fn count(&self, name: &str) -> usize {
+        self.get(name) as usize
+    }
+}
+
+pub const GENERATORS: &[Generator] = &[
+    Generator {
+        day: "day01",
+        knobs: &[
+            knob("elves", 250, 1, "number of elves"),
+            knob(
+                "max_items",
+                15,
+                1,
+                "maximum number of items carried by one elf",
+            ),
+            knob(
+                "max_calories",
+                60000,
+                1,
+                "maximum calories of a single item",
+            ),
+        ],
+        generate: day01,
+    },
+    Generator {
+        day: "day02",
+        knobs: &[knob(
+            "rounds",
+            2500,
+            1,
+            "number of rounds in the strategy guide",
+        )],
+        generate: day02,
+    },
+    Generator {
+        day: "day03",
+        knobs: &[
+            knob("groups", 100, 1, "number of elf groups"),
+            knob(
+                "group",
+                3,
+                2,
+                "number of rucksacks per group, solve with `day03 --group N`",
+            ),
+            knob("min_length", 16, 2, "minimum rucksack length"),
+            knob("max_length", 48, 2, "maximum rucksack length"),
+        ],
+        generate: day03,
+    },
+    Generator {
+        day: "day04",
+        knobs: &[
+            knob("pairs", 1000, 1, "number of elf pairs"),
+            knob("sections", 99, 1, "highest section ID"),
+        ],
+        generate: day04,
+    },
+    Generator {
+        day: "day05",
+        knobs: &[
+            knob("stacks", 9, 2, "number of stacks"),
+            knob("crates", 56, 1, "total number of crates in the drawing"),
+            knob("moves", 500, 1, "number of moves"),
+            knob("max_amount", 30, 1, "most crates moved in one move"),
+        ],
+        generate: day05,
+    },
+    Generator {
+        day: "day06",
+        knobs: &[knob("length", 4096, 18, "length of the datastream")],
+        generate: day06,
+    },
+    Generator {
+        day: "day07",
+        knobs: &[
+            knob("dirs", 180, 0, "number of directories besides /"),
+            knob("depth", 10, 1, "maximum directory depth"),
+            knob("files", 280, 0, "number of files"),
+            knob("max_size", 330000, 1, "maximum file size"),
+        ],
+        generate: day07,
+    },
+    Generator {
+        day: "day08",
+        knobs: &[
+            knob("rows", 99, 1, "grid height"),
+            knob("cols", 99, 1, "grid width"),
+        ],
+        generate: day08,
+    },
+    Generator {
+        day: "day09",
+        knobs: &[
+            knob("moves", 2000, 1, "number of head motions"),
+            knob("max_distance", 19, 1, "longest single motion"),
+        ],
+        generate: day09,
+    },
+    Generator {
+        day: "day10",
+        knobs: &[knob(
+            "cycles",
+            240,
+            240,
+            "minimum number of cycles the program runs",
+        )],
+        generate: day10,
+    },
+    Generator {
+        day: "day11",
+        knobs: &[
+            knob("monkeys", 8, 2, "number of monkeys"),
+            knob("max_items", 8, 1, "most starting items per monkey"),
+        ],
+        generate: day11,
+    },
+    Generator {
+        day: "day12",
+        knobs: &[
+            knob("rows", 41, 1, "grid height"),
+            knob("cols", 154, 1, "grid width"),
+            knob("walls", 10, 0, "percentage of cells turned into cliffs").up_to(100),
+        ],
+        generate: day12,
+    },
+    Generator {
+        day: "day13",
+        knobs: &[
+            knob("pairs", 150, 1, "number of packet pairs"),
+            knob("depth", 4, 0, "maximum list nesting"),
+            knob("width", 5, 0, "maximum list length"),
+            knob("max_value", 10, 0, "largest integer in a packet"),
+        ],
+        generate: day13,
+    },
+    Generator {
+        day: "day14",
+        knobs: &[
+            knob("paths", 137, 1, "number of rock paths"),
+            knob("segments", 6, 1, "most line segments per path"),
+            knob(
+                "width",
+                80,
+                2,
+                "horizontal spread of the rocks around x=500",
+            ),
+            knob("depth", 170, 2, "lowest rock"),
+        ],
+        generate: day14,
+    },
+    Generator {
+        day: "day15",
+        knobs: &[
+            knob("sensors", 28, 1, "number of sensors"),
+            knob(
+                "size",
+                4000000,
+                1,
+                "side of the square the distress beacon is hidden in",
+            ),
+        ],
+        generate: day15,
+    },
+    Generator {
+        day: "day16",
+        knobs: &[
+            knob("valves", 63, 2, "number of valves, including AA").up_to(26 * 26),
+            knob(
+                "flowing",
+                15,
+                0,
+                "number of valves with a non-zero flow rate",
+            ),
+            knob("max_rate", 25, 1, "highest flow rate"),
+            knob("tunnels", 15, 0, "extra tunnels on top of a spanning tree"),
+        ],
+        generate: day16,
+    },
+    Generator {
+        day: "day17",
+        knobs: &[knob("jets", 10091, 1, "length of the jet pattern")],
+        generate: day17,
+    },
+];
+
+fn day01(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let max_items = knobs.count("max_items") as i64;
+    let max_calories = knobs.get("max_calories");
+
+    Ok((0..knobs.count("elves"))
+        .map(|_| {
+            (0..rng.range(1, max_items))
+                .map(|_| rng.range(1, max_calories).to_string())
+                .collect::<Vec<String>>()
+                .join("\n")
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n"))
+}
+
+fn day02(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    Ok((0..knobs.count("rounds"))
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.pick(&['A', 'B', 'C']),
+                rng.pick(&['X', 'Y', 'Z'])
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+// every rucksack gets exactly one item type in both compartments,
+// and every group gets exactly one item type carried by all members
+fn day03(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let items: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let group_size = knobs.count("group");
+    if knobs.get("min_length") > knobs.get("max_length") {
+        return Err("--min_length can't be more than --max_length".to_string());
+    }
+    let max_len = knobs.count("max_length") / 2;
+    let min_len = knobs.count("min_length") / 2;
+
+    let mut rucksacks = Vec::new();
+    for _ in 0..knobs.count("groups") {
+        let badge = *rng.pick(&items);
+
+        // every other item type is forbidden in at least one member, so the badge stays unique
+        let mut forbidden = vec![HashSet::new(); group_size];
+        for item in &items {
+            if *item != badge {
+                forbidden[rng.below(group_size)].insert(*item);
+            }
+        }
+
+        for member_forbidden in forbidden {
+            let allowed: Vec<char> = items
+                .iter()
+                .filter(|item| **item != badge && !member_forbidden.contains(*item))
+                .copied()
+                .collect();
+            let half = rng.range(min_len as i64, max_len as i64) as usize;
+
+            // with a single item per compartment the badge has to be the shared item
+            let shared = if half == 1 || rng.chance(0.05) {
+                badge
+            } else {
+                *rng.pick(&allowed)
+            };
+
+            // split the remaining types over the compartments so they have nothing else in common
+            let mut left_pool = Vec::new();
+            let mut right_pool = Vec::new();
+            for item in allowed.iter().filter(|item| **item != shared) {
+                if rng.chance(0.5) {
+                    left_pool.push(*item);
+                } else {
+                    right_pool.push(*item);
+                }
+            }
+            for pool in [&mut left_pool, &mut right_pool] {
+                if pool.is_empty() {
+                    pool.push(shared);
+                }
+            }
+
+            let mut left = vec![shared];
+            let mut right = vec![shared];
+            if badge != shared {
+                if rng.chance(0.5) {
+                    left.push(badge);
+                    left_pool.push(badge);
+                } else {
+                    right.push(badge);
+                    right_pool.push(badge);
+                }
+            }
+            while left.len() < half {
+                left.push(*rng.pick(&left_pool));
+            }
+            while right.len() < half {
+                right.push(*rng.pick(&right_pool));
+            }
+            rng.shuffle(&mut left);
+            rng.shuffle(&mut right);
+
+            rucksacks.push(left.into_iter().chain(right).collect::<String>());
+        }
+    }
+
+    Ok(rucksacks.join("\n"))
+}
+
+fn day04(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let sections = knobs.get("sections");
+    let mut assignment = || {
+        let a = rng.range(1, sections);
+        let b = rng.range(1, sections);
+        (a.min(b), a.max(b))
+    };
+
+    Ok((0..knobs.count("pairs"))
+        .map(|_| {
+            let (a, b) = assignment();
+            let (c, d) = assignment();
+            format!("{}-{},{}-{}", a, b, c, d)
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+// stacks never run empty, so the tops can always be read at the end
+fn day05(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    // every stack starts with a crate, and some stack needs a second one to move
+    let stack_count = knobs.count("stacks");
+    let crate_count = knobs.count("crates");
+    if crate_count <= stack_count {
+        return Err("--crates has to be more than --stacks".to_string());
+    }
+    let max_amount = knobs.count("max_amount");
+
+    let mut stacks: Vec<Vec<char>> = vec![Vec::new(); stack_count];
+    for i in 0..crate_count {
+        let stack = if i < stack_count {
+            i
+        } else {
+            rng.below(stack_count)
+        };
+        stacks[stack].push((b'A' + rng.below(26) as u8) as char);
+    }
+
+    let mut lines = Vec::new();
+    let height = stacks.iter().map(|s| s.len()).max().unwrap();
+    for row in (0..height).rev() {
+        let line = stacks
+            .iter()
+            .map(|stack| match stack.get(row) {
+                Some(ch) => format!("[{}]", ch),
+                None => "   ".to_string(),
+            })
+            .collect::<Vec<String>>()
+            .join(" ");
+        lines.push(line.trim_end().to_string());
+    }
+    lines.push(
+        (1..=stack_count)
+            .map(|i| format!(" {:<3}", i))
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+            + " ",
+    );
+    lines.push(String::new());
+
+    // both crane models leave the same number of crates on each stack, so tracking sizes is enough.
+    // the moves keep a crate on every stack, so there is always a stack with one to spare
+    let mut sizes: Vec<usize> = stacks.iter().map(|s| s.len()).collect();
+    for _ in 0..knobs.get("moves") {
+        let from = loop {
+            let from = rng.below(stack_count);
+            if sizes[from] > 1 {
+                break from;
+            }
+        };
+        let to = (from + 1 + rng.below(stack_count - 1)) % stack_count;
+        let amount = rng.range(1, (sizes[from] - 1).min(max_amount) as i64) as usize;
+
+        sizes[from] -= amount;
+        sizes[to] += amount;
+        lines.push(format!("move {} from {} to {}", amount, from + 1, to + 1));
+    }
+
+    Ok(lines.join("\n"))
+}
+
+// the datastream is built so that both markers exist but do not show up right away:
+// first a run without 4 distinct characters in a row, then 4 fresh characters,
+// then a run without 14 distinct characters in a row, then 14 fresh characters
+fn day06(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let length = knobs.count("length");
+    let mut stream: Vec<u8> = Vec::with_capacity(length);
+
+    let letter = |rng: &mut Rng| b'a' + rng.below(26) as u8;
+    let repeating = |rng: &mut Rng, stream: &mut Vec<u8>, window: usize| {
+        let ch = if stream.is_empty() {
+            letter(rng)
+        } else {
+            let back = 1 + rng.below(window.min(stream.len()));
+            stream[stream.len() - back]
+        };
+        stream.push(ch);
+    };
+    let distinct = |rng: &mut Rng, stream: &mut Vec<u8>, n: usize| {
+        let mut letters: Vec<u8> = (b'a'..=b'z').collect();
+        rng.shuffle(&mut letters);
+        stream.extend_from_slice(&letters[..n]);
+    };
+
+    let first_run = rng.range(length as i64 / 10, length as i64 / 4) as usize;
+    let second_run = rng.range(length as i64 / 4, length as i64 / 2) as usize;
+
+    for _ in 0..first_run {
+        repeating(rng, &mut stream, 3);
+    }
+    distinct(rng, &mut stream, 4);
+    for _ in 0..second_run {
+        repeating(rng, &mut stream, 13);
+    }
+    distinct(rng, &mut stream, 14);
+    while stream.len() < length {
+        stream.push(letter(rng));
+    }
+    stream.truncate(length.max(first_run + second_run + 18));
+
+    Ok(String::from_utf8(stream).unwrap())
+}
+
+fn day07(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    struct Node {
+        name: String,
+        depth: usize,
+        children: Vec<usize>,
+        files: Vec<(i64, String)>,
+    }
+
+    let depth = knobs.count("depth");
+    let max_size = knobs.get("max_size");
+
+    let name = |rng: &mut Rng, len: usize| -> String {
+        (0..rng.range(1, len as i64))
+            .map(|_| (b'a' + rng.below(26) as u8) as char)
+            .collect()
+    };
+    let unique_name = |rng: &mut Rng, taken: &HashSet<String>| loop {
+        let mut n = name(rng, 8);
+        if rng.chance(0.4) {
+            n = format!("{}.{}", n, name(rng, 3));
+        }
+        if !taken.contains(&n) {
+            break n;
+        }
+    };
+
+    let mut nodes = vec![Node {
+        name: "/".to_string(),
+        depth: 0,
+        children: Vec::new(),
+        files: Vec::new(),
+    }];
+    let mut taken: Vec<HashSet<String>> = vec![HashSet::new()];
+
+    // a chain first so the depth knob is actually reached, then random directories
+    for i in 0..knobs.count("dirs") {
+        let parent = if i < depth {
+            i
+        } else {
+            loop {
+                let parent = rng.below(nodes.len());
+                if nodes[parent].depth < depth {
+                    break parent;
+                }
+            }
+        };
+
+        let dir_name = unique_name(rng, &taken[parent]);
+        taken[parent].insert(dir_name.clone());
+        taken.push(HashSet::new());
+        nodes.push(Node {
+            name: dir_name,
+            depth: nodes[parent].depth + 1,
+            children: Vec::new(),
+            files: Vec::new(),
+        });
+        let child = nodes.len() - 1;
+        nodes[parent].children.push(child);
+    }
+
+    // the files have to fit on the 70000000 disk of part 2, so sizes shrink when it fills up
+    const DISK: i64 = 70000000;
+    let files = knobs.get("files");
+    if files > DISK {
+        return Err(format!("at most {} files fit on the disk", DISK));
+    }
+    let mut used = 0;
+    for i in 0..files {
+        let dir = rng.below(nodes.len());
+        let file_name = unique_name(rng, &taken[dir]);
+        taken[dir].insert(file_name.clone());
+        let size = rng.range(1, max_size.min(DISK - used - (files - i - 1)));
+        used += size;
+        nodes[dir].files.push((size, file_name));
+    }
+
+    // walk the tree depth first, the way a person exploring it would
+    let mut lines = vec!["$ cd /".to_string()];
+    let mut stack = vec![(0, false)];
+    while let Some((dir, visited)) = stack.pop() {
+        if visited {
+            lines.push("$ cd ..".to_string());
+            continue;
+        }
+
+        if dir != 0 {
+            lines.push(format!("$ cd {}", nodes[dir].name));
+            stack.push((dir, true));
+        }
+
+        lines.push("$ ls".to_string());
+        let mut listing: Vec<String> = nodes[dir]
+            .children
+            .iter()
+            .map(|child| format!("dir {}", nodes[*child].name))
+            .chain(
+                nodes[dir]
+                    .files
+                    .iter()
+                    .map(|(size, name)| format!("{} {}", size, name)),
+            )
+            .collect();
+        rng.shuffle(&mut listing);
+        lines.extend(listing);
+
+        for child in nodes[dir].children.iter().rev() {
+            stack.push((*child, false));
+        }
+    }
+
+    // there is no point in climbing back out at the very end
+    while lines.last().map(|l| l == "$ cd ..").unwrap_or(false) {
+        lines.pop();
+    }
+
+    Ok(lines.join("\n"))
+}
+
+fn day08(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let cols = knobs.count("cols");
+
+    Ok((0..knobs.count("rows"))
+        .map(|_| {
+            (0..cols)
+                .map(|_| (b'0' + rng.below(10) as u8) as char)
+                .collect::<String>()
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+fn day09(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let max_distance = knobs.get("max_distance");
+
+    Ok((0..knobs.count("moves"))
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.pick(&['U', 'D', 'L', 'R']),
+                rng.range(1, max_distance)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+// the register takes a random walk that stays roughly on the 40 pixel wide screen
+fn day10(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let cycles = knobs.get("cycles");
+    let mut lines = Vec::new();
+    let mut cycle = 0;
+    let mut register: i64 = 1;
+
+    while cycle < cycles {
+        if rng.chance(0.3) {
+            lines.push("noop".to_string());
+            cycle += 1;
+            continue;
+        }
+
+        let mut v = rng.range(-15, 15);
+        if !(-5..=45).contains(&(register + v)) {
+            v = -v;
+        }
+        if v == 0 {
+            v = 1;
+        }
+        register += v;
+        lines.push(format!("addx {}", v));
+        cycle += 2;
+    }
+
+    Ok(lines.join("\n"))
+}
+
+// the monkeys test the first nine primes, so the product of the divisors squared still fits
+// an i64 and part 2 can keep the worry levels in check. part 1 has no such trick, so monkeys
+// whose worry levels overflow in its 20 rounds are rolled again
+fn day11(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    struct Monkey {
+        items: Vec<i64>,
+        // None squares the old worry level
+        factor: Option<i64>,
+        add: i64,
+        divisor: i64,
+        targets: (usize, usize),
+    }
+
+    let monkeys = knobs.count("monkeys");
+    let max_items = knobs.count("max_items") as i64;
+
+    let roll = |rng: &mut Rng| -> Vec<Monkey> {
+        let mut divisors = [2, 3, 5, 7, 11, 13, 17, 19, 23];
+        rng.shuffle(&mut divisors);
+
+        (0..monkeys)
+            .map(|i| {
+                let items = (0..rng.range(1, max_items))
+                    .map(|_| rng.range(50, 99))
+                    .collect();
+                let (factor, add) = match rng.below(5) {
+                    0 => (None, 0),
+                    1 | 2 => (Some(rng.range(2, 19)), 0),
+                    _ => (Some(1), rng.range(1, 8)),
+                };
+                let true_monkey = (i + 1 + rng.below(monkeys - 1)) % monkeys;
+                let false_monkey = loop {
+                    let m = rng.below(monkeys);
+                    if m != i && (m != true_monkey || monkeys == 2) {
+                        break m;
+                    }
+                };
+                Monkey {
+                    items,
+                    factor,
+                    add,
+                    divisor: divisors[i % divisors.len()],
+                    targets: (true_monkey, false_monkey),
+                }
+            })
+            .collect()
+    };
+
+    // part_1_fits plays the 20 rounds of part 1 with checked math
+    let part_1_fits = |monkeys: &[Monkey]| {
+        let mut items: Vec<Vec<i64>> = monkeys.iter().map(|m| m.items.clone()).collect();
+        for _ in 0..20 {
+            for (i, monkey) in monkeys.iter().enumerate() {
+                for old in std::mem::take(&mut items[i]) {
+                    let new = match monkey.factor {
+                        None => old.checked_mul(old),
+                        Some(f) => old.checked_mul(f),
+                    }
+                    .and_then(|w| w.checked_add(monkey.add));
+                    let Some(new) = new.map(|w| w / 3) else {
+                        return false;
+                    };
+                    let target = match new % monkey.divisor == 0 {
+                        true => monkey.targets.0,
+                        false => monkey.targets.1,
+                    };
+                    items[target].push(new);
+                }
+            }
+        }
+        true
+    };
+
+    let monkeys = loop {
+        let monkeys = roll(rng);
+        if part_1_fits(&monkeys) {
+            break monkeys;
+        }
+    };
+
+    Ok(monkeys
+        .iter()
+        .enumerate()
+        .map(|(i, monkey)| {
+            let items = monkey
+                .items
+                .iter()
+                .map(|w| w.to_string())
+                .collect::<Vec<String>>()
+                .join(", ");
+            let operation = match (monkey.factor, monkey.add) {
+                (None, _) => "old * old".to_string(),
+                (Some(1), add) => format!("old + {}", add),
+                (Some(f), _) => format!("old * {}", f),
+            };
+
+            format!(
+                "Monkey {}:\n  Starting items: {}\n  Operation: new = {}\n  Test: divisible by {}\n    If true: throw to monkey {}\n    If false: throw to monkey {}",
+                i,
+                items,
+                operation,
+                monkey.divisor,
+                monkey.targets.0,
+                monkey.targets.1
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n"))
+}
+
+// the elevation rises towards E by at most one per step, so there is always a path.
+// cliffs are sprinkled on top, but only kept when S can still reach E
+fn day12(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    // E has to be at least 25 steps away from S to fit all elevations in between
+    let rows = knobs.count("rows");
+    let cols = knobs.count("cols");
+    if rows + cols < 27 {
+        return Err("--rows and --cols have to add up to at least 27".to_string());
+    }
+    let walls = knobs.get("walls") as f64 / 100.0;
+
+    // S goes in the corner furthest from E, which the opposite corners are always far enough for
+    let (start, end) = loop {
+        let end = (rng.below(rows), rng.below(cols));
+        let start = [(0, 0), (0, cols - 1), (rows - 1, 0), (rows - 1, cols - 1)]
+            .into_iter()
+            .max_by_key(|p| manhattan_usize(*p, end))
+            .unwrap();
+        if manhattan_usize(start, end) >= 25 {
+            break (start, end);
+        }
+    };
+
+    // spread the 26 elevations over the longest distance
+    let max_distance = manhattan_usize(start, end);
+    let per_level = (max_distance / 26).max(1);
+    let base: Vec<Vec<u8>> = (0..rows)
+        .map(|r| {
+            (0..cols)
+                .map(|c| {
+                    let d = max_distance - manhattan_usize((r, c), end);
+                    (d / per_level).min(25) as u8
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut map = base.clone();
+    for attempt in 0..10 {
+        map = base.clone();
+        for (r, row) in map.iter_mut().enumerate() {
+            for (c, h) in row.iter_mut().enumerate() {
+                if (r, c) != start && (r, c) != end && rng.chance(walls / (attempt + 1) as f64) {
+                    *h = (*h + 3).min(25);
+                }
+            }
+        }
+        if reachable(&map, start, end) {
+            break;
+        }
+        map = base.clone();
+    }
+
+    let mut grid: Vec<Vec<char>> = map
+        .iter()
+        .map(|row| row.iter().map(|h| (b'a' + h) as char).collect())
+        .collect();
+    grid[start.0][start.1] = 'S';
+    grid[end.0][end.1] = 'E';
+
+    Ok(grid
+        .iter()
+        .map(|row| row.iter().collect())
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+fn manhattan_usize(a: (usize, usize), b: (usize, usize)) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+fn reachable(map: &[Vec<u8>], start: (usize, usize), end: (usize, usize)) -> bool {
+    let mut seen = HashSet::from([start]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(p) = queue.pop_front() {
+        if p == end {
+            return true;
+        }
+        let neighbors = [
+            (p.0.wrapping_sub(1), p.1),
+            (p.0 + 1, p.1),
+            (p.0, p.1.wrapping_sub(1)),
+            (p.0, p.1 + 1),
+        ];
+        for n in neighbors {
+            if n.0 < map.len()
+                && n.1 < map[0].len()
+                && map[n.0][n.1] <= map[p.0][p.1] + 1
+                && seen.insert(n)
+            {
+                queue.push_back(n);
+            }
+        }
+    }
+
+    false
+}
+
+fn day13(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    fn packet(rng: &mut Rng, depth: usize, width: usize, max_value: i64) -> String {
+        let items = (0..rng.range(0, width as i64))
+            .map(|_| {
+                if depth > 0 && rng.chance(0.3) {
+                    packet(rng, depth - 1, width, max_value)
+                } else {
+                    rng.range(0, max_value).to_string()
+                }
+            })
+            .collect::<Vec<String>>();
+
+        format!("[{}]", items.join(","))
+    }
+
+    let depth = knobs.count("depth");
+    let width = knobs.count("width");
+    let max_value = knobs.get("max_value");
+
+    Ok((0..knobs.count("pairs"))
+        .map(|_| {
+            format!(
+                "{}\n{}",
+                packet(rng, depth, width, max_value),
+                packet(rng, depth, width, max_value)
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n\n"))
+}
+
+fn day14(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let spread = knobs.get("width") / 2;
+    let depth = knobs.get("depth");
+    let segments = knobs.count("segments") as i64;
+
+    Ok((0..knobs.count("paths"))
+        .map(|_| {
+            // rocks start below the source of the sand
+            let mut p = (rng.range(500 - spread, 500 + spread), rng.range(2, depth));
+            let mut points = vec![p];
+            for _ in 0..rng.range(1, segments) {
+                loop {
+                    let step = rng.range(-8, 8);
+                    let next = if rng.chance(0.5) {
+                        (p.0 + step, p.1)
+                    } else {
+                        (p.0, p.1 + step)
+                    };
+                    if next != p && next.1 >= 2 && next.1 <= depth {
+                        p = next;
+                        break;
+                    }
+                }
+                points.push(p);
+            }
+
+            points
+                .iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .collect::<Vec<String>>()
+                .join(" -> ")
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+// each sensor gets the largest range that keeps the hidden distress beacon uncovered
+// and does not put any other beacon closer than its own.
+// with few sensors the rest of the square is not necessarily covered,
+// so other candidate spots for the distress beacon may exist
+fn day15(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let size = knobs.get("size");
+    let distress = (rng.range(0, size), rng.range(0, size));
+    let dist = |a: (i64, i64), b: (i64, i64)| (a.0 - b.0).abs() + (a.1 - b.1).abs();
+
+    let mut sensors: Vec<((i64, i64), (i64, i64))> = Vec::new();
+    let mut attempts = 0;
+    while sensors.len() < knobs.count("sensors") && attempts < 100000 {
+        attempts += 1;
+
+        let s = (rng.range(0, size), rng.range(0, size));
+        if s == distress || sensors.iter().any(|(other, b)| *other == s || *b == s) {
+            continue;
+        }
+
+        // the range is limited by the distress beacon and all known beacons
+        let limit = sensors
+            .iter()
+            .map(|(_, b)| dist(s, *b))
+            .fold(dist(s, distress) - 1, i64::min);
+        if limit < 1 {
+            continue;
+        }
+
+        // pick a beacon on the edge of that range, outside all other sensor ranges
+        let r = rng.range((limit / 2).max(1), limit);
+        let dx = rng.range(-r, r);
+        let dy = if rng.chance(0.5) {
+            r - dx.abs()
+        } else {
+            dx.abs() - r
+        };
+        let b = (s.0 + dx, s.1 + dy);
+        if sensors
+            .iter()
+            .any(|(other, other_b)| *other_b != b && dist(*other, b) <= dist(*other, *other_b))
+        {
+            continue;
+        }
+
+        sensors.push((s, b));
+    }
+
+    Ok(sensors
+        .iter()
+        .map(|(s, b)| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                s.0, s.1, b.0, b.1
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+fn day16(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    let count = knobs.count("valves");
+    let flowing = knobs.count("flowing");
+    if flowing >= count {
+        return Err("--flowing has to be less than --valves, AA never flows".to_string());
+    }
+    let max_rate = knobs.get("max_rate");
+
+    let mut names: Vec<String> = (0..26 * 26)
+        .map(|i| {
+            format!(
+                "{}{}",
+                (b'A' + (i / 26) as u8) as char,
+                (b'A' + (i % 26) as u8) as char
+            )
+        })
+        .filter(|n| n != "AA")
+        .collect();
+    rng.shuffle(&mut names);
+    names.truncate(count - 1);
+    names.insert(0, "AA".to_string());
+
+    // AA never has flow, the other valves are picked at random
+    let mut rates = vec![0; count];
+    let mut indices: Vec<usize> = (1..count).collect();
+    rng.shuffle(&mut indices);
+    for i in indices.into_iter().take(flowing) {
+        rates[i] = rng.range(1, max_rate);
+    }
+
+    // a random spanning tree keeps everything reachable from AA
+    let mut tunnels = vec![Vec::new(); count];
+    for i in 1..count {
+        let j = rng.below(i);
+        tunnels[i].push(j);
+        tunnels[j].push(i);
+    }
+    for _ in 0..knobs.get("tunnels") {
+        let (i, j) = (rng.below(count), rng.below(count));
+        if i != j && !tunnels[i].contains(&j) {
+            tunnels[i].push(j);
+            tunnels[j].push(i);
+        }
+    }
+
+    let mut lines: Vec<String> = (0..count)
+        .map(|i| {
+            let neighbors: Vec<&str> = tunnels[i].iter().map(|j| names[*j].as_str()).collect();
+            if neighbors.len() == 1 {
+                format!(
+                    "Valve {} has flow rate={}; tunnel leads to valve {}",
+                    names[i], rates[i], neighbors[0]
+                )
+            } else {
+                format!(
+                    "Valve {} has flow rate={}; tunnels lead to valves {}",
+                    names[i],
+                    rates[i],
+                    neighbors.join(", ")
+                )
+            }
+        })
+        .collect();
+    rng.shuffle(&mut lines);
+
+    Ok(lines.join("\n"))
+}
+
+fn day17(rng: &mut Rng, knobs: &Knobs) -> Result<String, String> {
+    Ok((0..knobs.count("jets"))
+        .map(|_| if rng.chance(0.5) { '<' } else { '>' })
+        .collect())
+}
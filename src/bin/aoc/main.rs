@@ -0,0 +1,125 @@
+use std::{env, fs, path::Path, process};
+
+use advent2022::rng::Rng;
+
+mod dash;
+mod gen;
+
+const USAGE: &str = "usage:
+  aoc gen <day> [--seed N] [--<knob> N]...   print a random input for a day
+  aoc gen <day> --help                       list the knobs of a day's generator
+  aoc dash                                   dashboard with the status of every day
+  aoc repl <day>                             explore a day's parsed input interactively";
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("gen") => run_gen(&args[1..]),
+        Some("dash") => dash::run(),
+        Some("repl") => run_repl(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn run_gen(args: &[String]) -> Result<(), String> {
+    let day_names = gen::GENERATORS
+        .iter()
+        .map(|g| g.day)
+        .collect::<Vec<&str>>()
+        .join(", ");
+
+    let day = args
+        .first()
+        .ok_or_else(|| format!("{}\n\ndays: {}", USAGE, day_names))?;
+    let generator = gen::GENERATORS
+        .iter()
+        .find(|g| g.day == day || g.day == format!("day{:0>2}", day))
+        .ok_or_else(|| format!("no generator for {}, pick one of: {}", day, day_names))?;
+
+    let mut seed = 0;
+    let mut overrides = Vec::new();
+    let mut rest = args[1..].iter();
+    while let Some(arg) = rest.next() {
+        if arg == "--help" {
+            println!("knobs for {}:", generator.day);
+            println!("  --seed N (default 0)");
+            for knob in generator.knobs {
+                println!(
+                    "  --{} N (default {}, {}): {}",
+                    knob.name,
+                    knob.default,
+                    knob.range(),
+                    knob.help
+                );
+            }
+            return Ok(());
+        }
+
+        let name = arg
+            .strip_prefix("--")
+            .ok_or_else(|| format!("unexpected argument `{}`", arg))?;
+        let value = rest
+            .next()
+            .ok_or_else(|| format!("missing value for --{}", name))?;
+
+        if name == "seed" {
+            seed = value
+                .parse()
+                .map_err(|_| format!("invalid seed `{}`", value))?;
+        } else {
+            let value = value
+                .parse()
+                .map_err(|_| format!("invalid value `{}` for --{}", value, name))?;
+            overrides.push((name.to_string(), value));
+        }
+    }
+
+    let knobs = gen::Knobs::new(generator, &overrides)?;
+    print!("{}", (generator.generate)(&mut Rng::new(seed), &knobs)?);
+
+    Ok(())
+}
+
+// repl_days lists the days that have a repl, which is every src/bin/<day> with a repl.rs
+fn repl_days() -> Vec<String> {
+    let mut days: Vec<String> = match fs::read_dir(Path::new("src").join("bin")) {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .filter(|e| e.path().join("repl.rs").exists())
+            .filter_map(|e| e.file_name().into_string().ok())
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    days.sort();
+    days
+}
+
+// run_repl starts the day's own binary in repl mode, it has the parser and the puzzle logic
+fn run_repl(args: &[String]) -> Result<(), String> {
+    let days = repl_days();
+    let day_names = days.join(", ");
+
+    let day = args
+        .first()
+        .ok_or_else(|| format!("{}\n\ndays: {}", USAGE, day_names))?;
+    let day = days
+        .iter()
+        .find(|d| *d == day || **d == format!("day{:0>2}", day))
+        .ok_or_else(|| format!("no repl for {}, pick one of: {}", day, day_names))?;
+
+    let status = process::Command::new(env::var("CARGO").unwrap_or_else(|_| "cargo".to_string()))
+        .args(["run", "-q", "--release", "--bin", day, "--", "repl"])
+        .status()
+        .map_err(|e| format!("unable to start {}: {}", day, e))?;
+    if !status.success() {
+        return Err(format!("{} exited with {}", day, status));
+    }
+
+    Ok(())
+}
@@ -1,34 +1,149 @@
-use std::fs;
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    env,
+    fs::File,
+    io::{BufRead, BufReader},
+};
+
+use advent2022::{fuzz, input_path};
+
+mod report;
 
 fn main() {
-    let input = fs::read_to_string("input/day01.txt").expect("unable to read file");
-
-    let mut inventories = Vec::new();
-    for block in input.split("\n\n") {
-        let mut inventory = Vec::new();
-        for line in block.split("\n") {
-            if line == "" {
-                continue;
-            }
+    fuzz::exit_if_requested("day01", |input| top_inventories(input.as_bytes(), 3));
+
+    // `day01 top 10` lists the ten biggest inventories instead of the puzzle answers,
+    // `day01 report [table|csv|json]` lists every elf
+    let args: Vec<String> = env::args().skip(1).collect();
+    let file = File::open(input_path("day01")).expect("unable to read file");
 
-            inventory.push(line.parse::<i64>().unwrap());
+    let top_n = match args.first().map(|s| s.as_str()) {
+        Some("top") => Some(
+            args.get(1)
+                .and_then(|n| n.parse::<usize>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or_else(|| panic!("usage: day01 top <n>, with n at least 1")),
+        ),
+        Some("report") => {
+            let format = report::Format::parse(args.get(1).map_or("table", |s| s.as_str()))
+                .unwrap_or_else(|e| panic!("{}", e));
+            let mut inventories = Vec::new();
+            read_inventories(BufReader::new(file), |inventory| {
+                inventories.push(inventory)
+            })
+            .unwrap_or_else(|e| panic!("invalid input: {}", e));
+            print!("{}", report::render(&inventories, format));
+            return;
         }
+        _ => None,
+    };
 
-        inventories.push(inventory);
+    let top = top_inventories(BufReader::new(file), top_n.unwrap_or(3))
+        .unwrap_or_else(|e| panic!("invalid input: {}", e));
+    let total = top
+        .iter()
+        .try_fold(0i64, |acc, calories| acc.checked_add(*calories))
+        .unwrap_or_else(|| panic!("invalid input: too many calories"));
+
+    if let Some(n) = top_n {
+        for (rank, calories) in top.iter().enumerate() {
+            println!("{:>4}. {}", rank + 1, calories);
+        }
+        println!("Top {} - {}", n, total);
+        return;
     }
 
-    let mut sums: Vec<i64> = inventories
-        .iter()
-        .map(|inventory| inventory.iter().sum())
-        .collect();
+    println!("Part 1 - {}", top.first().copied().unwrap_or(0));
+
+    println!("Part 2 - {}", total);
+}
 
-    let max_sum = sums.iter().max().unwrap();
+// Inventory is what one elf carries, elves are numbered from 1 in the order of the input
+#[derive(Debug, Clone, Copy)]
+struct Inventory {
+    elf: usize,
+    items: usize,
+    calories: i64,
+}
+
+// top_inventories only keeps the n biggest totals in a min-heap,
+// so memory stays the same however big the input is. The totals come back biggest first
+fn top_inventories(reader: impl BufRead, n: usize) -> Result<Vec<i64>, String> {
+    let mut top = BinaryHeap::with_capacity(n + 1);
+    read_inventories(reader, |inventory| {
+        keep_top(&mut top, n, inventory.calories)
+    })?;
 
-    println!("Part 1 - {}", max_sum);
+    let mut top: Vec<i64> = top.into_iter().map(|Reverse(total)| total).collect();
+    top.sort_unstable_by(|a, b| b.cmp(a));
+    Ok(top)
+}
 
-    sums.sort();
+// read_inventories reads the input one line at a time and hands every inventory to f
+// as soon as it is complete, without holding on to any of them
+fn read_inventories(mut reader: impl BufRead, mut f: impl FnMut(Inventory)) -> Result<(), String> {
+    let mut line = String::new();
+    let mut inventory: Option<Inventory> = None;
+    let mut elves = 0;
 
-    let sum_three: i64 = sums.iter().rev().take(3).sum();
+    for line_number in 1.. {
+        line.clear();
+        let read = reader
+            .read_line(&mut line)
+            .map_err(|e| format!("line {}: {}", line_number, e))?;
+        // files saved on Windows end their lines with \r\n
+        let text = line.strip_suffix('\n').unwrap_or(&line);
+        let text = text.strip_suffix('\r').unwrap_or(text);
 
-    println!("Part 2 - {}", sum_three);
+        // a blank line or the end of the input closes the inventory
+        if text.is_empty() {
+            if let Some(inventory) = inventory.take() {
+                f(inventory);
+            }
+            if read == 0 {
+                break;
+            }
+            continue;
+        }
+
+        let calories = text
+            .parse::<i64>()
+            .map_err(|_| format!("line {}: invalid calories `{}`", line_number, text))?;
+        if calories < 0 {
+            return Err(format!(
+                "line {}: negative calories `{}`",
+                line_number, text
+            ));
+        }
+
+        let current = inventory.get_or_insert_with(|| {
+            elves += 1;
+            Inventory {
+                elf: elves,
+                items: 0,
+                calories: 0,
+            }
+        });
+        current.items += 1;
+        current.calories = current
+            .calories
+            .checked_add(calories)
+            .ok_or_else(|| format!("line {}: too many calories", line_number))?;
+    }
+
+    Ok(())
+}
+
+// keep_top adds a total to the heap when it beats the smallest one kept so far
+fn keep_top(top: &mut BinaryHeap<Reverse<i64>>, n: usize, total: i64) {
+    if top.len() < n {
+        top.push(Reverse(total));
+    } else if top
+        .peek()
+        .is_some_and(|Reverse(smallest)| total > *smallest)
+    {
+        top.pop();
+        top.push(Reverse(total));
+    }
 }
@@ -0,0 +1,218 @@
+use std::fmt::Write;
+
+use super::Inventory;
+
+// The report lists every elf with its rank, 1 being the elf with the most calories,
+// elves with the same total share a rank. Below that come a few statistics over the totals.
+
+const PERCENTILES: [usize; 6] = [10, 25, 50, 75, 90, 99];
+const BUCKETS: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+impl Format {
+    pub fn parse(format: &str) -> Result<Format, String> {
+        match format {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => Err(format!(
+                "unknown format `{}`, use table, csv or json",
+                format
+            )),
+        }
+    }
+}
+
+struct Stats {
+    elves: usize,
+    calories: i128,
+    mean: f64,
+    min: i64,
+    max: i64,
+    median: f64,
+    percentiles: Vec<(usize, i64)>,
+    // every bucket covers the same range of totals, from min to max
+    histogram: Vec<(i64, i64, usize)>,
+}
+
+// ranks returns the rank of every inventory, in the same order
+fn ranks(inventories: &[Inventory]) -> Vec<usize> {
+    let mut sorted: Vec<i64> = inventories.iter().map(|i| i.calories).collect();
+    sorted.sort_unstable_by(|a, b| b.cmp(a));
+
+    inventories
+        .iter()
+        .map(|inventory| 1 + sorted.partition_point(|calories| *calories > inventory.calories))
+        .collect()
+}
+
+fn stats(inventories: &[Inventory]) -> Option<Stats> {
+    let mut sorted: Vec<i64> = inventories.iter().map(|i| i.calories).collect();
+    sorted.sort_unstable();
+    let (min, max) = (*sorted.first()?, *sorted.last()?);
+
+    let elves = sorted.len();
+    let calories: i128 = sorted.iter().map(|c| *c as i128).sum();
+    let median = if elves % 2 == 1 {
+        sorted[elves / 2] as f64
+    } else {
+        (sorted[elves / 2 - 1] as f64 + sorted[elves / 2] as f64) / 2.0
+    };
+
+    // nearest rank, the smallest total that at least p percent of the elves don't exceed
+    let percentiles = PERCENTILES
+        .iter()
+        .map(|p| (*p, sorted[(p * elves).div_ceil(100).max(1) - 1]))
+        .collect();
+
+    // BUCKETS buckets of the same width cover min to max, the last one can reach past max
+    let width = ((max - min + 1) as u64).div_ceil(BUCKETS as u64) as i64;
+    let mut histogram: Vec<(i64, i64, usize)> = (0..BUCKETS as i64)
+        .map(|b| (min + b * width, min + (b + 1) * width - 1, 0))
+        .take_while(|(from, _, _)| *from <= max)
+        .collect();
+    for calories in &sorted {
+        histogram[((calories - min) / width) as usize].2 += 1;
+    }
+
+    Some(Stats {
+        elves,
+        calories,
+        mean: calories as f64 / elves as f64,
+        min,
+        max,
+        median,
+        percentiles,
+        histogram,
+    })
+}
+
+pub fn render(inventories: &[Inventory], format: Format) -> String {
+    let ranks = ranks(inventories);
+    let stats = stats(inventories);
+
+    match format {
+        Format::Table => table(inventories, &ranks, stats.as_ref()),
+        Format::Csv => csv(inventories, &ranks),
+        Format::Json => json(inventories, &ranks, stats.as_ref()),
+    }
+}
+
+// table lists the elves by rank, with the statistics below
+fn table(inventories: &[Inventory], ranks: &[usize], stats: Option<&Stats>) -> String {
+    let mut order: Vec<usize> = (0..inventories.len()).collect();
+    order.sort_by_key(|i| (ranks[*i], inventories[*i].elf));
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "{:>6} {:>6} {:>6} {:>12}",
+        "rank", "elf", "items", "calories"
+    )
+    .unwrap();
+    for i in order {
+        let inventory = &inventories[i];
+        writeln!(
+            out,
+            "{:>6} {:>6} {:>6} {:>12}",
+            ranks[i], inventory.elf, inventory.items, inventory.calories
+        )
+        .unwrap();
+    }
+
+    let Some(stats) = stats else {
+        out.push_str("\nno elves\n");
+        return out;
+    };
+
+    writeln!(out).unwrap();
+    writeln!(out, "elves     {}", stats.elves).unwrap();
+    writeln!(out, "calories  {}", stats.calories).unwrap();
+    writeln!(out, "min       {}", stats.min).unwrap();
+    writeln!(out, "max       {}", stats.max).unwrap();
+    writeln!(out, "mean      {:.1}", stats.mean).unwrap();
+    writeln!(out, "median    {:.1}", stats.median).unwrap();
+    for (p, calories) in &stats.percentiles {
+        writeln!(out, "p{:<8} {}", p, calories).unwrap();
+    }
+
+    writeln!(out).unwrap();
+    let most = stats.histogram.iter().map(|b| b.2).max().unwrap_or(1);
+    for (from, to, elves) in &stats.histogram {
+        let bar = "#".repeat((elves * 40).div_ceil(most));
+        writeln!(out, "{:>12} - {:<12} {:>6} {}", from, to, elves, bar).unwrap();
+    }
+
+    out
+}
+
+// csv keeps the order of the input, the statistics are easy to redo from the rows
+fn csv(inventories: &[Inventory], ranks: &[usize]) -> String {
+    let mut out = String::from("elf,items,calories,rank\n");
+    for (inventory, rank) in inventories.iter().zip(ranks) {
+        writeln!(
+            out,
+            "{},{},{},{}",
+            inventory.elf, inventory.items, inventory.calories, rank
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn json(inventories: &[Inventory], ranks: &[usize], stats: Option<&Stats>) -> String {
+    let elves: Vec<String> = inventories
+        .iter()
+        .zip(ranks)
+        .map(|(inventory, rank)| {
+            format!(
+                "    {{\"elf\": {}, \"items\": {}, \"calories\": {}, \"rank\": {}}}",
+                inventory.elf, inventory.items, inventory.calories, rank
+            )
+        })
+        .collect();
+
+    let stats = match stats {
+        None => "null".to_string(),
+        Some(stats) => {
+            let percentiles: Vec<String> = stats
+                .percentiles
+                .iter()
+                .map(|(p, calories)| format!("\"p{}\": {}", p, calories))
+                .collect();
+            let histogram: Vec<String> = stats
+                .histogram
+                .iter()
+                .map(|(from, to, elves)| {
+                    format!(
+                        "{{\"from\": {}, \"to\": {}, \"elves\": {}}}",
+                        from, to, elves
+                    )
+                })
+                .collect();
+            format!(
+                "{{\n    \"elves\": {},\n    \"calories\": {},\n    \"min\": {},\n    \"max\": {},\n    \"mean\": {},\n    \"median\": {},\n    \"percentiles\": {{{}}},\n    \"histogram\": [{}]\n  }}",
+                stats.elves,
+                stats.calories,
+                stats.min,
+                stats.max,
+                stats.mean,
+                stats.median,
+                percentiles.join(", "),
+                histogram.join(", ")
+            )
+        }
+    };
+
+    format!(
+        "{{\n  \"elves\": [\n{}\n  ],\n  \"stats\": {}\n}}\n",
+        elves.join(",\n"),
+        stats
+    )
+}
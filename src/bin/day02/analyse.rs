@@ -0,0 +1,178 @@
+use std::fs;
+
+use advent2022::rng::Rng;
+
+use super::{calc_all_points, parse_part_1, parse_part_2, points, split_line, Matchup};
+use crate::game::{Game, Outcome, Shape};
+
+// best_response is the shape that scores the most against an opponent, counting
+// the points for the shape as well as for the outcome
+fn best_response(game: &Game, point_matrix: &[Vec<i64>], opponent: Shape) -> Shape {
+    game.all_shapes()
+        .max_by_key(|player| points(point_matrix, &(opponent, *player)))
+        .expect("a game has shapes")
+}
+
+// optimise plays the best response in every round and compares that
+// with both readings of the guide, shapes like part 1 and outcomes like part 2
+pub fn optimise(game: &Game, input: &str, rounds: bool) -> Result<(), String> {
+    let point_matrix = game.point_matrix();
+    let as_shapes = parse_part_1(game, input)?;
+    let as_outcomes = parse_part_2(game, input)?;
+
+    let best: Vec<Matchup> = as_shapes
+        .iter()
+        .map(|(opponent, _)| (*opponent, best_response(game, &point_matrix, *opponent)))
+        .collect();
+
+    if rounds {
+        println!(
+            "{:>6} {:>10} {:>10} {:>10} {:>6}",
+            "round", "opponent", "guide", "best", "gain"
+        );
+        for (i, (guide, best)) in as_shapes.iter().zip(&best).enumerate() {
+            println!(
+                "{:>6} {:>10} {:>10} {:>10} {:>6}",
+                i + 1,
+                game.shapes[guide.0 .0],
+                game.shapes[guide.1 .0],
+                game.shapes[best.1 .0],
+                points(&point_matrix, best) - points(&point_matrix, guide)
+            );
+        }
+        println!();
+    }
+
+    for opponent in game.all_shapes() {
+        let response = best_response(game, &point_matrix, opponent);
+        println!(
+            "against {:<10} play {:<10} for {} points",
+            game.shapes[opponent.0],
+            game.shapes[response.0],
+            points(&point_matrix, &(opponent, response))
+        );
+    }
+
+    let best_score = calc_all_points(&point_matrix, &best);
+    println!();
+    println!("best play      {}", best_score);
+    for (reading, matchups) in [("shapes", &as_shapes), ("outcomes", &as_outcomes)] {
+        let score = calc_all_points(&point_matrix, matchups);
+        println!(
+            "guide {:<8} {} (best play gains {})",
+            reading,
+            score,
+            best_score - score
+        );
+    }
+
+    Ok(())
+}
+
+// Record counts the rounds a guide won, drew and lost
+#[derive(Debug, Default, Clone, Copy)]
+struct Record {
+    wins: usize,
+    draws: usize,
+    losses: usize,
+}
+
+impl Record {
+    fn add(&mut self, outcome: Outcome) {
+        match outcome {
+            Outcome::Win => self.wins += 1,
+            Outcome::Draw => self.draws += 1,
+            Outcome::Lose => self.losses += 1,
+        }
+    }
+}
+
+// tournament lets every guide play every other guide round by round, reading the second
+// column as the shape to play, and then plays each guide against opponents that pick shapes at random
+pub fn tournament(game: &Game, args: &[String]) -> Result<(), String> {
+    let mut files = Vec::new();
+    let (mut runs, mut seed) = (1000, 0);
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        let target = match arg.as_str() {
+            "--runs" => &mut runs,
+            "--seed" => &mut seed,
+            _ => {
+                files.push(arg);
+                continue;
+            }
+        };
+        let value = rest
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+        *target = value
+            .parse()
+            .map_err(|_| format!("invalid value `{}` for {}", value, arg))?;
+    }
+    if files.len() < 2 {
+        return Err("usage: day02 tournament <guide> <guide>... [--runs N] [--seed N]".to_string());
+    }
+
+    let guides: Vec<Vec<Shape>> = files
+        .iter()
+        .map(|file| {
+            let input =
+                fs::read_to_string(file).map_err(|e| format!("unable to read {}: {}", file, e))?;
+            player_column(game, &input).map_err(|e| format!("invalid guide {}: {}", file, e))
+        })
+        .collect::<Result<_, String>>()?;
+
+    let mut records = vec![Record::default(); guides.len()];
+    for a in 0..guides.len() {
+        for b in a + 1..guides.len() {
+            // guides of different lengths play as many rounds as the shorter one has
+            for (shape_a, shape_b) in guides[a].iter().zip(&guides[b]) {
+                let outcome = game.outcome(*shape_b, *shape_a);
+                records[a].add(outcome);
+                records[b].add(game.outcome(*shape_a, *shape_b));
+            }
+        }
+    }
+
+    let point_matrix = game.point_matrix();
+    let mut rng = Rng::new(seed);
+    println!(
+        "{:<24} {:>7} {:>7} {:>7} {:>14} {:>14}",
+        "guide", "wins", "draws", "losses", "random (sim)", "random (exact)"
+    );
+    for ((file, guide), record) in files.iter().zip(&guides).zip(&records) {
+        let mut total = 0;
+        for _ in 0..runs {
+            for player in guide {
+                let opponent = Shape(rng.below(game.size()));
+                total += points(&point_matrix, &(opponent, *player));
+            }
+        }
+        let simulated = total as f64 / runs.max(1) as f64;
+
+        // every shape wins against half of the others and loses against the other half,
+        // so a random opponent is worth the average outcome points in every round
+        let outcome_points: i64 = point_matrix[0].iter().sum();
+        let exact: f64 = guide
+            .iter()
+            .map(|player| player.points() as f64 + outcome_points as f64 / game.size() as f64)
+            .sum();
+
+        println!(
+            "{:<24} {:>7} {:>7} {:>7} {:>14.1} {:>14.1}",
+            file, record.wins, record.draws, record.losses, simulated, exact
+        );
+    }
+
+    Ok(())
+}
+
+fn player_column(game: &Game, input: &str) -> Result<Vec<Shape>, String> {
+    input
+        .split("\n")
+        .map(|line| {
+            let (_, player) = split_line(line)?;
+            game.player_shape(player)
+        })
+        .collect()
+}
@@ -0,0 +1,181 @@
+use std::collections::HashSet;
+
+// Game is a cyclic game like Rock Paper Scissors with any odd number of shapes.
+// The shapes are listed in a circle where every shape beats the (n - 1) / 2 shapes before it,
+// so with Rock Paper Scissors, Paper beats Rock, Scissors beats Paper and Rock beats Scissors.
+// Rock Paper Scissors Lizard Spock works the same way in the order Rock Spock Paper Lizard Scissors.
+// A shape scores its position in the list, counting from 1, and the symbols that
+// show up in the strategy guide are part of the game too. A config looks like:
+//
+//   # comment
+//   shapes Rock Paper Scissors
+//   opponent A B C
+//   player X Y Z
+//   outcomes X Y Z
+//
+// `opponent` and `player` are the symbols for every shape in the first and second column,
+// `outcomes` are the symbols for lose, draw and win when the second column is read as an outcome.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Shape(pub usize);
+
+impl Shape {
+    pub fn points(&self) -> i64 {
+        self.0 as i64 + 1
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    Lose,
+    Draw,
+    Win,
+}
+
+impl Outcome {
+    pub const ALL: [Outcome; 3] = [Outcome::Lose, Outcome::Draw, Outcome::Win];
+
+    pub fn points(&self) -> i64 {
+        match self {
+            Self::Lose => 0,
+            Self::Draw => 3,
+            Self::Win => 6,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Game {
+    pub shapes: Vec<String>,
+    pub opponent: Vec<String>,
+    pub player: Vec<String>,
+    pub outcomes: Vec<String>,
+}
+
+impl Game {
+    pub fn classic() -> Game {
+        Game::parse("shapes Rock Paper Scissors\nopponent A B C\nplayer X Y Z\noutcomes X Y Z")
+            .expect("the classic game is valid")
+    }
+
+    pub fn parse(config: &str) -> Result<Game, String> {
+        let (mut shapes, mut opponent, mut player, mut outcomes) = (None, None, None, None);
+
+        for (i, line) in config.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or("").trim();
+            if line.is_empty() {
+                continue;
+            }
+
+            let mut words = line.split_whitespace();
+            let key = words.next().unwrap_or("");
+            let values: Vec<String> = words.map(|w| w.to_string()).collect();
+            let field = match key {
+                "shapes" => &mut shapes,
+                "opponent" => &mut opponent,
+                "player" => &mut player,
+                "outcomes" => &mut outcomes,
+                _ => return Err(format!("line {}: unknown key `{}`", i + 1, key)),
+            };
+            if field.replace(values).is_some() {
+                return Err(format!("line {}: `{}` is set twice", i + 1, key));
+            }
+        }
+
+        let missing = |key: &str| format!("missing `{}`", key);
+        let game = Game {
+            shapes: shapes.ok_or_else(|| missing("shapes"))?,
+            opponent: opponent.ok_or_else(|| missing("opponent"))?,
+            player: player.ok_or_else(|| missing("player"))?,
+            outcomes: outcomes.ok_or_else(|| missing("outcomes"))?,
+        };
+
+        let n = game.shapes.len();
+        if n < 3 || n.is_multiple_of(2) {
+            return Err(format!(
+                "a cyclic game needs an odd number of shapes, at least 3, not {}",
+                n
+            ));
+        }
+        for (key, symbols, len) in [
+            ("shapes", &game.shapes, n),
+            ("opponent", &game.opponent, n),
+            ("player", &game.player, n),
+            ("outcomes", &game.outcomes, 3),
+        ] {
+            if symbols.len() != len {
+                return Err(format!(
+                    "`{}` needs {} symbols, not {}",
+                    key,
+                    len,
+                    symbols.len()
+                ));
+            }
+            if symbols.iter().collect::<HashSet<_>>().len() != len {
+                return Err(format!("`{}` has the same symbol twice", key));
+            }
+        }
+
+        Ok(game)
+    }
+
+    pub fn size(&self) -> usize {
+        self.shapes.len()
+    }
+
+    pub fn all_shapes(&self) -> impl Iterator<Item = Shape> {
+        (0..self.size()).map(Shape)
+    }
+
+    // outcome is the result for the player, counting how far the player is ahead in the circle
+    pub fn outcome(&self, opponent: Shape, player: Shape) -> Outcome {
+        let ahead = (player.0 + self.size() - opponent.0) % self.size();
+        if ahead == 0 {
+            Outcome::Draw
+        } else if ahead <= self.size() / 2 {
+            Outcome::Win
+        } else {
+            Outcome::Lose
+        }
+    }
+
+    // respond picks the shape right after or right before the opponent for a win or a loss,
+    // with more than three shapes there are others that do the same
+    pub fn respond(&self, opponent: Shape, outcome: Outcome) -> Shape {
+        match outcome {
+            Outcome::Lose => Shape((opponent.0 + self.size() - 1) % self.size()),
+            Outcome::Draw => opponent,
+            Outcome::Win => Shape((opponent.0 + 1) % self.size()),
+        }
+    }
+
+    // point_matrix holds the outcome points of the player for every (opponent, player) pair
+    pub fn point_matrix(&self) -> Vec<Vec<i64>> {
+        self.all_shapes()
+            .map(|opponent| {
+                self.all_shapes()
+                    .map(|player| self.outcome(opponent, player).points())
+                    .collect()
+            })
+            .collect()
+    }
+
+    pub fn opponent_shape(&self, symbol: &str) -> Result<Shape, String> {
+        find(&self.opponent, symbol, "opponent").map(Shape)
+    }
+
+    pub fn player_shape(&self, symbol: &str) -> Result<Shape, String> {
+        find(&self.player, symbol, "player").map(Shape)
+    }
+
+    pub fn outcome_of(&self, symbol: &str) -> Result<Outcome, String> {
+        find(&self.outcomes, symbol, "outcome").map(|i| Outcome::ALL[i])
+    }
+}
+
+fn find(symbols: &[String], symbol: &str, what: &str) -> Result<usize, String> {
+    symbols
+        .iter()
+        .position(|s| s == symbol)
+        .ok_or_else(|| format!("unknown {} symbol `{}`", what, symbol))
+}
@@ -0,0 +1,177 @@
+use super::split_line;
+use crate::game::{Game, Outcome, Shape};
+
+// The second column of the guide can mean a shape or an outcome, and nothing says which
+// symbol means what. run tries every way to map the symbols to shapes and to outcomes,
+// scores the guide for each and, given the score someone expects, points out the ones that match.
+
+// every mapping of the player's symbols to shapes is a reading of its own and gets printed,
+// so past this many shapes, 9! is 362880 readings, there are too many to go through
+const MAX_SHAPES: usize = 9;
+
+struct Reading {
+    kind: &'static str,
+    mapping: String,
+    score: i64,
+}
+
+pub fn run(game: &Game, input: &str, expected: Option<i64>) -> Result<(), String> {
+    let readings = readings(game, input)?;
+
+    for reading in &readings {
+        let matches = expected == Some(reading.score);
+        println!(
+            "{} {:<8} {:<40} {}",
+            if matches { "*" } else { " " },
+            reading.kind,
+            reading.mapping,
+            reading.score
+        );
+    }
+
+    if let Some(expected) = expected {
+        let matching = readings.iter().filter(|r| r.score == expected).count();
+        println!();
+        match matching {
+            0 => {
+                let closest = readings
+                    .iter()
+                    .min_by_key(|r| (r.score - expected).abs())
+                    .expect("there is always a reading");
+                println!(
+                    "no reading scores {}, the closest is {} {} with {}",
+                    expected, closest.kind, closest.mapping, closest.score
+                );
+            }
+            1 => println!("one reading scores {}, marked with *", expected),
+            n => println!("{} readings score {}, marked with *", n, expected),
+        }
+    }
+
+    Ok(())
+}
+
+// readings scores every mapping, counting the guide once up front so that
+// trying a mapping only costs a pass over the counts
+fn readings(game: &Game, input: &str) -> Result<Vec<Reading>, String> {
+    let n = game.size();
+    if n > MAX_SHAPES {
+        return Err(format!(
+            "infer tries every mapping of the symbols to shapes, which are {}! for {} shapes, \
+             it only goes up to {} shapes",
+            n, n, MAX_SHAPES
+        ));
+    }
+    let point_matrix = game.point_matrix();
+
+    // counts[opponent][symbol] for both readings of the second column, a reading
+    // is ruled out as soon as the guide has a symbol it doesn't know
+    let mut shape_counts = Some(vec![vec![0i64; n]; n]);
+    let mut outcome_counts = Some(vec![vec![0i64; 3]; n]);
+    for line in input.split("\n") {
+        let (opponent, symbol) = split_line(line).map_err(|e| format!("invalid input: {}", e))?;
+        let opponent = game
+            .opponent_shape(opponent)
+            .map_err(|e| format!("invalid input: {}", e))?;
+        count(&mut shape_counts, opponent, &game.player, symbol);
+        count(&mut outcome_counts, opponent, &game.outcomes, symbol);
+        if shape_counts.is_none() && outcome_counts.is_none() {
+            return Err(format!("invalid input: unknown symbol `{}`", symbol));
+        }
+    }
+
+    let mut readings = Vec::new();
+
+    for shapes in permutations(n)
+        .into_iter()
+        .filter(|_| shape_counts.is_some())
+    {
+        let score = scores(
+            shape_counts.as_deref().unwrap_or_default(),
+            |opponent, symbol| {
+                let player = Shape(shapes[symbol]);
+                player.points() + point_matrix[opponent][player.0]
+            },
+        );
+        let mapping = game
+            .player
+            .iter()
+            .zip(&shapes)
+            .map(|(symbol, shape)| format!("{}={}", symbol, game.shapes[*shape]))
+            .collect::<Vec<String>>()
+            .join(" ");
+        readings.push(Reading {
+            kind: "shapes",
+            mapping,
+            score,
+        });
+    }
+
+    for outcomes in permutations(3)
+        .into_iter()
+        .filter(|_| outcome_counts.is_some())
+    {
+        let score = scores(
+            outcome_counts.as_deref().unwrap_or_default(),
+            |opponent, symbol| {
+                let outcome = Outcome::ALL[outcomes[symbol]];
+                game.respond(Shape(opponent), outcome).points() + outcome.points()
+            },
+        );
+        let mapping = game
+            .outcomes
+            .iter()
+            .zip(&outcomes)
+            .map(|(symbol, outcome)| format!("{}={:?}", symbol, Outcome::ALL[*outcome]))
+            .collect::<Vec<String>>()
+            .join(" ");
+        readings.push(Reading {
+            kind: "outcomes",
+            mapping,
+            score,
+        });
+    }
+
+    Ok(readings)
+}
+
+fn count(counts: &mut Option<Vec<Vec<i64>>>, opponent: Shape, symbols: &[String], symbol: &str) {
+    match symbols.iter().position(|s| s == symbol) {
+        Some(i) => {
+            if let Some(counts) = counts {
+                counts[opponent.0][i] += 1;
+            }
+        }
+        None => *counts = None,
+    }
+}
+
+fn scores(counts: &[Vec<i64>], points: impl Fn(usize, usize) -> i64) -> i64 {
+    counts
+        .iter()
+        .enumerate()
+        .flat_map(|(opponent, row)| {
+            row.iter()
+                .enumerate()
+                .map(move |(symbol, count)| (opponent, symbol, *count))
+        })
+        .map(|(opponent, symbol, count)| count * points(opponent, symbol))
+        .sum()
+}
+
+// permutations lists every order of 0..n, the identity first
+fn permutations(n: usize) -> Vec<Vec<usize>> {
+    if n == 0 {
+        return vec![Vec::new()];
+    }
+
+    let mut all = Vec::new();
+    for rest in permutations(n - 1) {
+        for at in (0..n).rev() {
+            let mut permutation = rest.clone();
+            permutation.insert(at, n - 1);
+            all.push(permutation);
+        }
+    }
+    all
+}
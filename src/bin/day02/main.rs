@@ -1,125 +1,112 @@
-use std::{collections::HashMap, fs};
+use std::{env, fs};
 
-#[derive(PartialEq, Eq, Hash)]
-enum Shape {
-    Rock,
-    Paper,
-    Scissors,
-}
+use advent2022::{fuzz, input_path};
 
-impl Shape {
-    fn points(&self) -> i64 {
-        match self {
-            Self::Rock => 1,
-            Self::Paper => 2,
-            Self::Scissors => 3,
-        }
-    }
+use game::{Game, Shape};
 
-    fn parse(ch: char) -> Self {
-        match ch {
-            'A' => Self::Rock,
-            'B' => Self::Paper,
-            'C' => Self::Scissors,
-            'X' => Self::Rock,
-            'Y' => Self::Paper,
-            'Z' => Self::Scissors,
-            x => panic!("cannot parse char {}", x),
-        }
-    }
+mod analyse;
+mod game;
+mod infer;
 
-    fn winning_matchup(opponent: &Self) -> Self {
-        match opponent {
-            Self::Rock => Self::Paper,
-            Self::Paper => Self::Scissors,
-            Self::Scissors => Self::Rock,
-        }
-    }
+type Matchup = (Shape, Shape);
 
-    fn draw_matchup(opponent: &Self) -> Self {
-        match opponent {
-            Self::Rock => Self::Rock,
-            Self::Paper => Self::Paper,
-            Self::Scissors => Self::Scissors,
-        }
-    }
-
-    fn losing_matchup(opponent: &Self) -> Self {
-        match opponent {
-            Self::Rock => Self::Scissors,
-            Self::Paper => Self::Rock,
-            Self::Scissors => Self::Paper,
-        }
-    }
-}
-
-fn points(point_matrix: &HashMap<(Shape, Shape), i64>, matchup: &(Shape, Shape)) -> i64 {
-    matchup.1.points() + point_matrix.get(matchup).expect("unknown matchup")
-}
-
-fn make_point_matrix() -> HashMap<(Shape, Shape), i64> {
-    HashMap::from([
-        ((Shape::Rock, Shape::Rock), 3),
-        ((Shape::Rock, Shape::Paper), 6),
-        ((Shape::Rock, Shape::Scissors), 0),
-        ((Shape::Paper, Shape::Rock), 0),
-        ((Shape::Paper, Shape::Paper), 3),
-        ((Shape::Paper, Shape::Scissors), 6),
-        ((Shape::Scissors, Shape::Rock), 6),
-        ((Shape::Scissors, Shape::Paper), 0),
-        ((Shape::Scissors, Shape::Scissors), 3),
-    ])
+fn points(point_matrix: &[Vec<i64>], matchup: &Matchup) -> i64 {
+    matchup.1.points() + point_matrix[matchup.0 .0][matchup.1 .0]
 }
 
 fn main() {
-    let input = fs::read_to_string("input/day02.txt").expect("unable to read file");
+    let classic = Game::classic();
+    fuzz::exit_if_requested("day02", |input| {
+        parse_part_1(&classic, input).and(parse_part_2(&classic, input))
+    });
+
+    // `day02 --game <config>` plays any cyclic game, see game.rs for the config
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    let game = match args.iter().position(|a| a == "--game") {
+        Some(i) => {
+            let file = args
+                .get(i + 1)
+                .expect("usage: day02 --game <config>")
+                .clone();
+            args.drain(i..i + 2);
+            let config = fs::read_to_string(file).expect("unable to read game config");
+            Game::parse(&config).unwrap_or_else(|e| panic!("invalid game config: {}", e))
+        }
+        None => classic,
+    };
+
+    let input = fs::read_to_string(input_path("day02")).expect("unable to read file");
+
+    // `day02 optimise [rounds]` compares the guide with the best possible play,
+    // `day02 tournament <guide>... [--runs N] [--seed N]` lets guides play each other,
+    // `day02 infer [score]` tries every way to read the second column
+    match args.first().map(|s| s.as_str()) {
+        Some("optimise") => {
+            let rounds = args.get(1).is_some_and(|a| a == "rounds");
+            analyse::optimise(&game, &input, rounds)
+                .unwrap_or_else(|e| panic!("invalid input: {}", e));
+            return;
+        }
+        Some("tournament") => {
+            analyse::tournament(&game, &args[1..]).unwrap_or_else(|e| panic!("{}", e));
+            return;
+        }
+        Some("infer") => {
+            let expected = args.get(1).map(|e| {
+                e.parse::<i64>()
+                    .unwrap_or_else(|_| panic!("invalid expected score `{}`", e))
+            });
+            infer::run(&game, &input, expected).unwrap_or_else(|e| panic!("{}", e));
+            return;
+        }
+        _ => {}
+    }
 
-    let point_matrix = make_point_matrix();
+    let point_matrix = game.point_matrix();
 
-    let mut matchups = parse_part_1(&input);
+    let mut matchups =
+        parse_part_1(&game, &input).unwrap_or_else(|e| panic!("invalid input: {}", e));
     let score1 = calc_all_points(&point_matrix, &matchups);
 
     println!("Part 1 - {}", score1);
 
-    matchups = parse_part_2(&input);
+    matchups = parse_part_2(&game, &input).unwrap_or_else(|e| panic!("invalid input: {}", e));
     let score2 = calc_all_points(&point_matrix, &matchups);
 
     println!("Part 2 - {}", score2);
-
 }
 
-fn parse_part_1(input: &String) -> Vec<(Shape, Shape)> {
+fn parse_part_1(game: &Game, input: &str) -> Result<Vec<Matchup>, String> {
     input
         .split("\n")
         .map(|line| {
-            let ch: Vec<char> = line.chars().collect();
-            (Shape::parse(ch[0]), Shape::parse(ch[2]))
+            let (opponent, player) = split_line(line)?;
+            Ok((game.opponent_shape(opponent)?, game.player_shape(player)?))
         })
         .collect()
 }
 
-fn parse_part_2(input: &String) -> Vec<(Shape, Shape)> {
+fn parse_part_2(game: &Game, input: &str) -> Result<Vec<Matchup>, String> {
     input
         .split("\n")
         .map(|line| {
-            let ch: Vec<char> = line.chars().collect();
-            let opponent = Shape::parse(ch[0]);
-            let player = match ch[2] {
-                'X' => Shape::losing_matchup(&opponent),
-                'Y' => Shape::draw_matchup(&opponent),
-                'Z' => Shape::winning_matchup(&opponent),
-                x => panic!("cannot parse char {}", x),
-            };
-            (opponent, player)
+            let (opponent, outcome) = split_line(line)?;
+            let opponent = game.opponent_shape(opponent)?;
+            Ok((opponent, game.respond(opponent, game.outcome_of(outcome)?)))
         })
         .collect()
 }
 
-fn calc_all_points(
-    point_matrix: &HashMap<(Shape, Shape), i64>,
-    matchups: &Vec<(Shape, Shape)>,
-) -> i64 {
+// split_line checks that a line looks like `A X`, symbols can be longer than one letter
+fn split_line(line: &str) -> Result<(&str, &str), String> {
+    match line.split_once(' ') {
+        Some((a, b)) if !a.is_empty() && !b.is_empty() && !b.contains(' ') => Ok((a, b)),
+        _ => Err(format!("invalid line `{}`", line)),
+    }
+}
+
+fn calc_all_points(point_matrix: &[Vec<i64>], matchups: &[Matchup]) -> i64 {
     matchups
         .iter()
-        .fold(0, |acc, matchup| acc + points(&point_matrix, matchup))
+        .fold(0, |acc, matchup| acc + points(point_matrix, matchup))
 }
@@ -0,0 +1,174 @@
+use std::time::{Duration, Instant};
+
+use advent2022::rng::Rng;
+
+use super::{part_1, part_2, priority};
+
+// run times both parts on generated rucksacks, next to the nested loops
+// the parts used to be, for rucksacks short enough that those finish
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (mut length, mut count, mut group_size, mut seed) = (48, 30_000, 3, 0);
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        let target = match arg.as_str() {
+            "--length" => &mut length,
+            "--rucksacks" => &mut count,
+            "--group" => &mut group_size,
+            "--seed" => &mut seed,
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        };
+        let value = rest
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+        *target = value
+            .parse()
+            .map_err(|_| format!("invalid value `{}` for {}", value, arg))?;
+    }
+    if length < 4 || length % 2 == 1 || group_size < 2 {
+        return Err(
+            "rucksacks need an even number of items, at least 4, and groups at least 2 rucksacks"
+                .to_string(),
+        );
+    }
+
+    let rucksacks = generate(&mut Rng::new(seed as u64), length, count, group_size);
+    let items = rucksacks.len() * length;
+    println!(
+        "{} rucksacks of {} items in groups of {}",
+        rucksacks.len(),
+        length,
+        group_size
+    );
+
+    let (answer_1, time_1) = time(|| part_1(&rucksacks));
+    let (answer_2, time_2) = time(|| part_2(&rucksacks, group_size));
+    let (answer_1, answer_2) = (answer_1?, answer_2?);
+    report("bitset part 1", answer_1, time_1, items);
+    report("bitset part 2", answer_2, time_2, items);
+
+    if length <= 1000 {
+        let (naive_1, time_1) = time(|| naive_part_1(&rucksacks));
+        let (naive_2, time_2) = time(|| naive_part_2(&rucksacks, group_size));
+        report("loops  part 1", naive_1, time_1, items);
+        report("loops  part 2", naive_2, time_2, items);
+        if (naive_1, naive_2) != (answer_1, answer_2) {
+            return Err("the bitset and loop answers differ".to_string());
+        }
+    } else {
+        println!("loops  skipped, they are quadratic in the rucksack length");
+    }
+
+    Ok(())
+}
+
+fn time<T>(f: impl Fn() -> T) -> (T, Duration) {
+    let started = Instant::now();
+    let answer = f();
+    (answer, started.elapsed())
+}
+
+fn report(name: &str, answer: i64, time: Duration, items: usize) {
+    println!(
+        "{} - {:>12} in {:>10.2?} ({:.0} items/s)",
+        name,
+        answer,
+        time,
+        items as f64 / time.as_secs_f64()
+    );
+}
+
+// naive_part_1 is the way part 1 used to be, comparing every pair of items
+fn naive_part_1(rucksacks: &[Vec<char>]) -> i64 {
+    rucksacks
+        .iter()
+        .map(|rucksack| {
+            let (first, second) = rucksack.split_at(rucksack.len() / 2);
+            first
+                .iter()
+                .find(|item_type| second.contains(item_type))
+                .and_then(|item_type| priority(*item_type))
+                .unwrap_or(0)
+        })
+        .sum()
+}
+
+// naive_part_2 looks for the badge by checking every item of the first rucksack in all the others
+fn naive_part_2(rucksacks: &[Vec<char>], group_size: usize) -> i64 {
+    rucksacks
+        .chunks(group_size)
+        .map(|group| {
+            group[0]
+                .iter()
+                .find(|item_type| group[1..].iter().all(|r| r.contains(item_type)))
+                .and_then(|item_type| priority(*item_type))
+                .unwrap_or(0)
+        })
+        .sum()
+}
+
+// generate makes rucksacks that share exactly one item type between compartments
+// and exactly one badge per group, like the real input, but of any length
+fn generate(rng: &mut Rng, length: usize, count: usize, group_size: usize) -> Vec<Vec<char>> {
+    let item_types: Vec<char> = ('a'..='z').chain('A'..='Z').collect();
+    let half = length / 2;
+
+    let mut rucksacks = Vec::new();
+    for _ in 0..count / group_size {
+        let badge = *rng.pick(&item_types);
+
+        // every other item type is left out by at least one member, so the badge stays unique
+        let mut forbidden = vec![Vec::new(); group_size];
+        for item_type in &item_types {
+            if *item_type != badge {
+                forbidden[rng.below(group_size)].push(*item_type);
+            }
+        }
+
+        for forbidden in forbidden {
+            let allowed: Vec<char> = item_types
+                .iter()
+                .filter(|t| **t != badge && !forbidden.contains(t))
+                .cloned()
+                .collect();
+            let shared = if allowed.is_empty() || rng.chance(0.1) {
+                badge
+            } else {
+                *rng.pick(&allowed)
+            };
+
+            // apart from the shared type the compartments never have a type in common
+            let (left, right): (Vec<char>, Vec<char>) = allowed
+                .into_iter()
+                .filter(|t| *t != shared)
+                .partition(|_| rng.chance(0.5));
+
+            let mut first = vec![shared, badge];
+            first.dedup();
+            let mut second = vec![shared];
+            fill(rng, &mut first, &left, shared, half);
+            fill(rng, &mut second, &right, shared, half);
+            first.extend(second);
+            rucksacks.push(first);
+        }
+    }
+
+    rucksacks
+}
+
+// fill tops a compartment up to its size and shuffles it
+fn fill(
+    rng: &mut Rng,
+    compartment: &mut Vec<char>,
+    item_types: &[char],
+    shared: char,
+    size: usize,
+) {
+    while compartment.len() < size {
+        let item_type = match item_types.is_empty() {
+            true => shared,
+            false => *rng.pick(item_types),
+        };
+        compartment.push(item_type);
+    }
+    rng.shuffle(compartment);
+}
@@ -1,63 +1,166 @@
-use std::fs;
+use std::{env, fs, process};
+
+use advent2022::{fuzz, input_path};
+
+mod bench;
+mod validate;
+
+// Items is the set of item types in a rucksack or compartment, bit n is set for priority n
+type Items = u64;
 
 fn main() {
-    let input = fs::read_to_string("input/day03.txt").expect("unable to read file");
+    fuzz::exit_if_requested("day03", |input: &str| parse_rucksacks(input, 3));
+
+    // `day03 --group N` changes the size of a group, `day03 validate` lists every problem
+    // with the input instead of solving it, `day03 bench [--length N] [--rucksacks N] [--group N] [--seed N]` times generated rucksacks
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|a| a == "bench") {
+        bench::run(&args[1..]).unwrap_or_else(|e| panic!("{}", e));
+        return;
+    }
+    let group_size = match args.iter().position(|a| a == "--group") {
+        Some(i) => args
+            .get(i + 1)
+            .and_then(|n| n.parse().ok())
+            .filter(|n| *n > 0)
+            .expect("usage: day03 --group <size>"),
+        None => 3,
+    };
+
+    let input = fs::read_to_string(input_path("day03")).expect("unable to read file");
+
+    let findings = validate::validate(&input, group_size);
+    if args.first().is_some_and(|a| a == "validate") {
+        print!("{}", validate::report(&input, group_size, &findings));
+        process::exit(if findings.is_empty() { 0 } else { 1 });
+    }
+    // when solving, problems that leave more than one item type to pick from are only warnings
+    eprint!("{}", validate::problems(&findings));
+    if findings.iter().any(|f| f.blocking) {
+        process::exit(1);
+    }
 
-    println!("Part 1 - {}", part_1(&input));
-    println!("Part 2 - {}", part_2(&input));
+    let answers = parse_rucksacks(&input, group_size)
+        .and_then(|rucksacks| Ok((part_1(&rucksacks)?, part_2(&rucksacks, group_size)?)));
+    match answers {
+        Ok((answer_1, answer_2)) => {
+            println!("Part 1 - {}", answer_1);
+            println!("Part 2 - {}", answer_2);
+        }
+        Err(e) => {
+            eprintln!("invalid input: {}", e);
+            process::exit(1);
+        }
+    }
 }
 
-fn part_1(input: &String) -> i64 {
-    input
+// parse_rucksacks checks that every rucksack splits into two compartments of item types,
+// and that the rucksacks split into groups
+fn parse_rucksacks(input: &str, group_size: usize) -> Result<Vec<Vec<char>>, String> {
+    let rucksacks: Vec<Vec<char>> = input
         .split("\n")
-        .map(|line| {
-            let rucksack: Vec<char> = line.chars().collect();
-            let mut compartments = rucksack.chunks(rucksack.len() / 2);
-            let first_compartment = compartments.next().unwrap();
-            let second_compartment = compartments.next().unwrap();
-
-            for item_type_first in first_compartment {
-                for item_type_right in second_compartment {
-                    if item_type_first == item_type_right {
-                        return priority(*item_type_first);
-                    }
-                }
+        .enumerate()
+        .map(|(i, line)| {
+            if line.is_empty() || line.len() % 2 != 0 {
+                return Err(format!("line {}: odd number of items", i + 1));
+            }
+            if let Some(ch) = line.chars().find(|ch| !ch.is_ascii_alphabetic()) {
+                return Err(format!("line {}: invalid item type `{}`", i + 1, ch));
             }
+            Ok(line.chars().collect())
+        })
+        .collect::<Result<_, _>>()?;
+
+    if !rucksacks.len().is_multiple_of(group_size) {
+        return Err(format!(
+            "rucksacks don't split into groups of {}",
+            group_size
+        ));
+    }
 
-            panic!("no match found between the two compartments");
+    Ok(rucksacks)
+}
+
+fn part_1(rucksacks: &[Vec<char>]) -> Result<i64, String> {
+    rucksacks
+        .iter()
+        .enumerate()
+        .map(|(i, rucksack)| {
+            let (first_compartment, second_compartment) = rucksack.split_at(rucksack.len() / 2);
+            let shared = items(first_compartment) & items(second_compartment);
+            if shared == 0 {
+                return Err(format!(
+                    "line {}: no match found between the two compartments",
+                    i + 1
+                ));
+            }
+            Ok(lowest_priority(shared))
         })
         .sum()
 }
 
-fn part_2(input: &String) -> i64 {
-    input
-        .split("\n")
-        .collect::<Vec<&str>>()
-        .chunks(3)
-        .map(|lines| {
-            for item_type_1 in lines[0].chars() {
-                for item_type_2 in lines[1].chars() {
-                    if item_type_1 == item_type_2 {
-                        for item_type_3 in lines[2].chars() {
-                            if item_type_3 == item_type_1 {
-                                return priority(item_type_1);
-                            }
-                        }
-                    }
-                }
+fn part_2(rucksacks: &[Vec<char>], group_size: usize) -> Result<i64, String> {
+    rucksacks
+        .chunks(group_size)
+        .enumerate()
+        .map(|(g, group)| {
+            let badge = group
+                .iter()
+                .fold(Items::MAX, |common, rucksack| common & items(rucksack));
+            if badge == 0 {
+                return Err(format!(
+                    "lines {}-{}: no badge found between the rucksacks of a group",
+                    g * group_size + 1,
+                    g * group_size + group.len()
+                ));
             }
-
-            panic!("no badge found between the three rucksacks");
+            Ok(lowest_priority(badge))
         })
         .sum()
 }
 
-fn priority(item_type: char) -> i64 {
-    if item_type >= 'a' && item_type <= 'z' {
-        item_type as i64 - 'a' as i64 + 1
-    } else if item_type >= 'A' && item_type <= 'Z' {
-        item_type as i64 - 'A' as i64 + 27
+fn items(rucksack: &[char]) -> Items {
+    rucksack
+        .iter()
+        .fold(0, |items, item_type| items | item_mask(*item_type))
+}
+
+// item_mask looks the bit up in a table, which keeps the loop in items free of branches.
+// Anything that isn't an item type has no bit
+fn item_mask(item_type: char) -> Items {
+    const MASKS: [Items; 128] = {
+        let mut masks = [0; 128];
+        let mut i = 0;
+        while i < 26 {
+            masks[b'a' as usize + i] = 1 << (i + 1);
+            masks[b'A' as usize + i] = 1 << (i + 27);
+            i += 1;
+        }
+        masks
+    };
+
+    MASKS.get(item_type as usize).copied().unwrap_or(0)
+}
+
+// lowest_priority picks the first item type in a set, puzzle inputs only ever share one
+fn lowest_priority(items: Items) -> i64 {
+    items.trailing_zeros() as i64
+}
+
+fn priority(item_type: char) -> Option<i64> {
+    if item_type.is_ascii_lowercase() {
+        Some(item_type as i64 - 'a' as i64 + 1)
+    } else if item_type.is_ascii_uppercase() {
+        Some(item_type as i64 - 'A' as i64 + 27)
     } else {
-        panic!("invalid item type");
+        None
     }
 }
+
+// item_types lists the item types in a set, lowest priority first
+fn item_types(items: Items) -> Vec<char> {
+    ('a'..='z')
+        .chain('A'..='Z')
+        .filter(|item_type| items & item_mask(*item_type) != 0)
+        .collect()
+}
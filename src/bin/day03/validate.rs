@@ -0,0 +1,226 @@
+use std::fmt::Write;
+
+use super::{item_types, items, priority, Items};
+
+// Finding is one problem with the input, for one rucksack or for a whole group.
+// A blocking one leaves nothing to answer with, the others leave more than one item type
+// to pick from
+#[derive(Debug)]
+pub struct Finding {
+    pub first_line: usize,
+    pub last_line: usize,
+    pub problem: String,
+    pub blocking: bool,
+}
+
+impl Finding {
+    fn blocking(first_line: usize, last_line: usize, problem: String) -> Finding {
+        Finding {
+            first_line,
+            last_line,
+            problem,
+            blocking: true,
+        }
+    }
+
+    fn ambiguous(first_line: usize, last_line: usize, problem: String) -> Finding {
+        Finding {
+            first_line,
+            last_line,
+            problem,
+            blocking: false,
+        }
+    }
+}
+
+// validate checks every rucksack and every group, and keeps going after a problem
+// so the report has all of them. A rucksack needs an even number of letters with exactly
+// one item type in both compartments, a group exactly one item type carried by all members.
+pub fn validate(input: &str, group_size: usize) -> Vec<Finding> {
+    let lines: Vec<&str> = input.split("\n").collect();
+    let mut findings = Vec::new();
+
+    // the item types of every rucksack, counting only the valid ones
+    let mut rucksacks: Vec<Items> = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        let line_number = i + 1;
+        let line_items: Vec<char> = line.chars().collect();
+        rucksacks.push(items(&line_items));
+
+        if line_items.is_empty() {
+            findings.push(Finding::blocking(
+                line_number,
+                line_number,
+                "empty rucksack".to_string(),
+            ));
+            continue;
+        }
+
+        let mut invalid: Vec<char> = line_items
+            .iter()
+            .filter(|item_type| priority(**item_type).is_none())
+            .cloned()
+            .collect();
+        invalid.sort_unstable();
+        invalid.dedup();
+        if !invalid.is_empty() {
+            findings.push(Finding::blocking(
+                line_number,
+                line_number,
+                format!("invalid item types {}", list(&invalid)),
+            ));
+        }
+
+        if line_items.len() % 2 == 1 {
+            findings.push(Finding::blocking(
+                line_number,
+                line_number,
+                format!("odd number of items ({})", line_items.len()),
+            ));
+            continue;
+        }
+
+        let (first, second) = line_items.split_at(line_items.len() / 2);
+        let shared = item_types(items(first) & items(second));
+        match shared.len() {
+            0 => findings.push(Finding::blocking(
+                line_number,
+                line_number,
+                "no item type in both compartments".to_string(),
+            )),
+            1 => {}
+            _ => findings.push(Finding::ambiguous(
+                line_number,
+                line_number,
+                format!("item types {} are in both compartments", list(&shared)),
+            )),
+        }
+    }
+
+    for (g, group) in rucksacks.chunks(group_size).enumerate() {
+        let first_line = g * group_size + 1;
+        let last_line = first_line + group.len() - 1;
+        if group.len() < group_size {
+            findings.push(Finding::blocking(
+                first_line,
+                last_line,
+                format!(
+                    "the last group has {} rucksacks instead of {}",
+                    group.len(),
+                    group_size
+                ),
+            ));
+            continue;
+        }
+
+        let badges = item_types(
+            group
+                .iter()
+                .fold(Items::MAX, |common, items| common & items),
+        );
+        match badges.len() {
+            0 => findings.push(Finding::blocking(
+                first_line,
+                last_line,
+                "no badge in the group".to_string(),
+            )),
+            1 => {}
+            _ => findings.push(Finding::ambiguous(
+                first_line,
+                last_line,
+                format!("item types {} could all be the badge", list(&badges)),
+            )),
+        }
+    }
+
+    findings
+}
+
+// report lists the findings with the offending lines, or says that all is fine
+pub fn report(input: &str, group_size: usize, findings: &[Finding]) -> String {
+    let lines: Vec<&str> = input.split("\n").collect();
+    let groups = lines.len().div_ceil(group_size);
+
+    let mut out = String::new();
+    for finding in findings {
+        if finding.first_line == finding.last_line {
+            writeln!(out, "line {}: {}", finding.first_line, finding.problem).unwrap();
+            writeln!(out, "    {}", lines[finding.first_line - 1]).unwrap();
+        } else {
+            writeln!(
+                out,
+                "lines {}-{}: {}",
+                finding.first_line, finding.last_line, finding.problem
+            )
+            .unwrap();
+        }
+    }
+
+    if findings.is_empty() {
+        writeln!(
+            out,
+            "all {} rucksacks in {} groups of {} are fine",
+            lines.len(),
+            groups,
+            group_size
+        )
+        .unwrap();
+    } else {
+        writeln!(
+            out,
+            "{} problems in {} rucksacks and {} groups of {}",
+            findings.len(),
+            lines.len(),
+            groups,
+            group_size
+        )
+        .unwrap();
+    }
+    out
+}
+
+// problems lists the findings in one line each, for when the input gets solved.
+// The blocking ones are errors, the rest are warnings because the answers take the first
+// item type wherever there is more than one to pick from
+pub fn problems(findings: &[Finding]) -> String {
+    let level = |blocking: bool| if blocking { "error" } else { "warning" };
+    let mut out = String::new();
+    for finding in findings {
+        match finding.first_line == finding.last_line {
+            true => writeln!(
+                out,
+                "{}: line {}: {}",
+                level(finding.blocking),
+                finding.first_line,
+                finding.problem
+            ),
+            false => writeln!(
+                out,
+                "{}: lines {}-{}: {}",
+                level(finding.blocking),
+                finding.first_line,
+                finding.last_line,
+                finding.problem
+            ),
+        }
+        .unwrap();
+    }
+    if !findings.is_empty() {
+        writeln!(
+            out,
+            "{}: {} problems, `day03 validate` shows them all",
+            level(findings.iter().any(|f| f.blocking)),
+            findings.len()
+        )
+        .unwrap();
+    }
+    out
+}
+
+fn list(item_types: &[char]) -> String {
+    item_types
+        .iter()
+        .map(|item_type| format!("`{}`", item_type))
+        .collect::<Vec<String>>()
+        .join(", ")
+}
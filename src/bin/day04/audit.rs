@@ -0,0 +1,79 @@
+use advent2022::interval::{max_overlap, IntervalSet, IntervalTree};
+
+use super::Group;
+
+// run reports on all assignments at once: how much gets cleaned, which sections
+// nobody cleans, and the most elves on one section. Elves are named after their
+// line and position, so 12:2 is the second elf on line 12.
+pub fn run(groups: &[Group], sections: &[i64]) {
+    let elves: Vec<(String, (i64, i64))> = groups
+        .iter()
+        .enumerate()
+        .flat_map(|(i, group)| {
+            group
+                .iter()
+                .enumerate()
+                .map(move |(j, range)| (format!("{}:{}", i + 1, j + 1), *range))
+        })
+        .collect();
+    let ranges: Vec<(i64, i64)> = elves.iter().map(|(_, range)| *range).collect();
+
+    let covered = IntervalSet::from_ranges(ranges.iter().cloned());
+    let Some((first, last)) = covered
+        .ranges()
+        .first()
+        .zip(covered.ranges().last())
+        .map(|(first, last)| (first.0.min(1), last.1))
+    else {
+        println!("no elves");
+        return;
+    };
+    let uncovered = covered.gaps((first, last));
+
+    println!("elves      {}", elves.len());
+    println!("sections   {}-{}", first, last);
+    println!("covered    {} sections", covered.len());
+    println!(
+        "uncovered  {} sections{}",
+        uncovered.len(),
+        if uncovered.is_empty() {
+            String::new()
+        } else {
+            format!(": {}", format_ranges(&uncovered))
+        }
+    );
+    if let Some((most, section)) = max_overlap(&ranges) {
+        println!("most elves {} on section {}", most, section);
+    }
+
+    if sections.is_empty() {
+        return;
+    }
+    println!();
+    let tree = IntervalTree::new(
+        elves
+            .iter()
+            .map(|(name, range)| (*range, name.as_str()))
+            .collect(),
+    );
+    for section in sections {
+        let names: Vec<&str> = tree.stab(*section).into_iter().cloned().collect();
+        println!(
+            "section {} is cleaned by {} elves: {}",
+            section,
+            names.len(),
+            names.join(", ")
+        );
+    }
+}
+
+fn format_ranges(set: &IntervalSet) -> String {
+    set.ranges()
+        .iter()
+        .map(|(start, end)| match start == end {
+            true => start.to_string(),
+            false => format!("{}-{}", start, end),
+        })
+        .collect::<Vec<String>>()
+        .join(", ")
+}
@@ -0,0 +1,138 @@
+use advent2022::interval::IntervalSet;
+
+use super::Group;
+
+// Analysis of one group of elves, members are numbered from 1 in the order of the line
+struct Analysis {
+    // (member, covered by) for every member whose sections another member also cleans
+    contained: Vec<(usize, usize)>,
+    // members that can go without the group cleaning any less
+    redundant: Vec<usize>,
+    // sections cleaned by more than one member
+    overlap: u64,
+}
+
+fn analyse(group: &[(i64, i64)]) -> Analysis {
+    let mut contained = Vec::new();
+    for (i, member) in group.iter().enumerate() {
+        for (j, other) in group.iter().enumerate() {
+            if i != j && other.0 <= member.0 && member.1 <= other.1 {
+                contained.push((i + 1, j + 1));
+            }
+        }
+    }
+
+    // drop members one by one while the rest still covers them, so of two
+    // members with the same sections only one goes
+    let mut kept: Vec<bool> = vec![true; group.len()];
+    let mut redundant = Vec::new();
+    for i in 0..group.len() {
+        let others = IntervalSet::from_ranges(
+            (0..group.len())
+                .filter(|j| *j != i && kept[*j])
+                .map(|j| group[j]),
+        );
+        if others.covers(&IntervalSet::from_ranges([group[i]])) {
+            kept[i] = false;
+            redundant.push(i + 1);
+        }
+    }
+
+    let overlaps = (0..group.len())
+        .flat_map(|i| (i + 1..group.len()).map(move |j| (i, j)))
+        .map(|(i, j)| intersection(group[i], group[j]))
+        .filter(|(start, end)| start <= end);
+
+    Analysis {
+        contained,
+        redundant,
+        overlap: IntervalSet::from_ranges(overlaps).len(),
+    }
+}
+
+fn intersection(a: (i64, i64), b: (i64, i64)) -> (i64, i64) {
+    (a.0.max(b.0), a.1.min(b.1))
+}
+
+// summary lists the groups where members overlap, with totals at the end
+pub fn summary(groups: &[Group]) {
+    println!(
+        "{:>6} {:>8} {:<20} {:<12} {:>8}",
+        "line", "members", "contained", "redundant", "overlap"
+    );
+
+    let (mut contained, mut redundant, mut overlap) = (0, 0, 0);
+    for (i, group) in groups.iter().enumerate() {
+        let analysis = analyse(group);
+        contained += analysis.contained.len();
+        redundant += analysis.redundant.len();
+        overlap += analysis.overlap;
+        if analysis.overlap == 0 {
+            continue;
+        }
+
+        let pairs: Vec<String> = analysis
+            .contained
+            .iter()
+            .map(|(member, by)| format!("{} in {}", member, by))
+            .collect();
+        println!(
+            "{:>6} {:>8} {:<20} {:<12} {:>8}",
+            i + 1,
+            group.len(),
+            pairs.join(", "),
+            join(&analysis.redundant),
+            analysis.overlap
+        );
+    }
+
+    println!();
+    println!("groups             {}", groups.len());
+    println!("contained members  {}", contained);
+    println!("redundant members  {}", redundant);
+    println!("overlapping        {} sections", overlap);
+}
+
+// detail shows one group with the sections every two members share
+pub fn detail(line: usize, group: &[(i64, i64)]) {
+    println!("line {}", line);
+    for (i, (start, end)) in group.iter().enumerate() {
+        println!("  member {}: {}-{}", i + 1, start, end);
+    }
+
+    println!();
+    print!("{:>8}", "shared");
+    for j in 0..group.len() {
+        print!("{:>6}", j + 1);
+    }
+    println!();
+    for i in 0..group.len() {
+        print!("{:>8}", i + 1);
+        for j in 0..group.len() {
+            let (start, end) = intersection(group[i], group[j]);
+            print!("{:>6}", if start <= end { end - start + 1 } else { 0 });
+        }
+        println!();
+    }
+
+    let analysis = analyse(group);
+    println!();
+    for (member, by) in &analysis.contained {
+        println!("member {} is covered by member {}", member, by);
+    }
+    if !analysis.redundant.is_empty() {
+        println!(
+            "members {} can go without losing any sections",
+            join(&analysis.redundant)
+        );
+    }
+    println!("{} sections are cleaned more than once", analysis.overlap);
+}
+
+fn join(members: &[usize]) -> String {
+    members
+        .iter()
+        .map(|m| m.to_string())
+        .collect::<Vec<String>>()
+        .join(", ")
+}
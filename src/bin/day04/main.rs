@@ -1,30 +1,73 @@
-use std::{fs, str::Chars};
+use std::{env, fs};
+
+use advent2022::{fuzz, input_path};
+
+mod audit;
+mod groups;
+
+// the section ranges of a group of elves, usually a pair
+type Group = Vec<(i64, i64)>;
 
 fn main() {
-    let input = fs::read_to_string("input/day04.txt").expect("unable to read file");
+    fuzz::exit_if_requested("day04", parse_lines);
 
-    println!("Part 1 - {}", part_1(&input));
-    println!("Part 2 - {}", part_2(&input));
-}
+    let input = fs::read_to_string(input_path("day04")).expect("unable to read file");
+    let groups = parse_lines(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
 
-fn part_1(input: &String) -> i64 {
-    let pairs = parse_lines(input);
+    // `day04 audit [section]...` looks at the whole roster, and at who cleans the given sections,
+    // `day04 groups` looks inside every group and `day04 group <line>` at one of them
+    let args: Vec<String> = env::args().skip(1).collect();
+    match args.first().map(|a| a.as_str()) {
+        Some("audit") => {
+            let sections: Vec<i64> = args[1..]
+                .iter()
+                .map(|a| {
+                    a.parse()
+                        .unwrap_or_else(|_| panic!("invalid section `{}`", a))
+                })
+                .collect();
+            audit::run(&groups, &sections);
+            return;
+        }
+        Some("groups") => {
+            groups::summary(&groups);
+            return;
+        }
+        Some("group") => {
+            let line = args
+                .get(1)
+                .and_then(|a| a.parse::<usize>().ok())
+                .filter(|line| (1..=groups.len()).contains(line))
+                .unwrap_or_else(|| panic!("usage: day04 group <line>, 1 to {}", groups.len()));
+            groups::detail(line, &groups[line - 1]);
+            return;
+        }
+        _ => {}
+    }
 
-    pairs
+    println!("Part 1 - {}", part_1(&groups));
+    println!("Part 2 - {}", part_2(&groups));
+}
+
+// part_1 counts the groups where one elf's sections are all covered by another elf
+fn part_1(groups: &[Group]) -> i64 {
+    groups
         .iter()
-        .filter(|ranges| full_overlap(ranges.0, ranges.1))
+        .filter(|group| any_pair(group, full_overlap))
         .count() as i64
 }
 
-fn part_2(input: &String) -> i64 {
-    let pairs = parse_lines(input);
-
-    pairs
+fn part_2(groups: &[Group]) -> i64 {
+    groups
         .iter()
-        .filter(|ranges| any_overlap(ranges.0, ranges.1))
+        .filter(|group| any_pair(group, any_overlap))
         .count() as i64
 }
 
+fn any_pair(group: &[(i64, i64)], f: fn((i64, i64), (i64, i64)) -> bool) -> bool {
+    (0..group.len()).any(|i| (i + 1..group.len()).any(|j| f(group[i], group[j])))
+}
+
 fn full_overlap(range_1: (i64, i64), range_2: (i64, i64)) -> bool {
     (range_1.0 <= range_2.0 && range_1.1 >= range_2.1)
         || (range_1.0 >= range_2.0 && range_1.1 <= range_2.1)
@@ -35,37 +78,36 @@ fn any_overlap(range_1: (i64, i64), range_2: (i64, i64)) -> bool {
         || (range_1.0 > range_2.0 && range_1.0 <= range_2.1)
 }
 
-fn parse_lines(input: &String) -> Vec<((i64, i64), (i64, i64))> {
+// parse_lines reads a group of comma separated ranges from every line, like `2-4,6-8`
+fn parse_lines(input: &str) -> Result<Vec<Group>, String> {
     input
         .split("\n")
-        .map(|line| {
-            let mut chars = line.chars();
-            (
-                (parse_number(&mut chars), parse_number(&mut chars)),
-                (parse_number(&mut chars), parse_number(&mut chars)),
-            )
+        .enumerate()
+        .map(|(i, line)| {
+            line.split(',')
+                .map(parse_range)
+                .collect::<Result<Group, String>>()
+                .map_err(|e| format!("line {}: {}", i + 1, e))
         })
         .collect()
 }
 
-fn parse_number(stream: &mut Chars) -> i64 {
-    let mut chars = Vec::new();
-    loop {
-        match stream.next() {
-            Some(c) => {
-                if c.is_numeric() {
-                    chars.push(c)
-                } else {
-                    break;
-                }
-            }
-            None => break,
-        }
+fn parse_range(range: &str) -> Result<(i64, i64), String> {
+    let (start, end) = range
+        .split_once('-')
+        .ok_or_else(|| format!("invalid range `{}`", range))?;
+    let range = (parse_number(start)?, parse_number(end)?);
+    if range.0 > range.1 {
+        return Err(format!("range {}-{} runs backwards", range.0, range.1));
     }
+    Ok(range)
+}
 
-    chars
-        .iter()
-        .collect::<String>()
+fn parse_number(number: &str) -> Result<i64, String> {
+    if number.is_empty() || !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("could not parse int `{}`", number));
+    }
+    number
         .parse::<i64>()
-        .expect("could not parse int")
+        .map_err(|_| format!("could not parse int `{}`", number))
 }
@@ -0,0 +1,112 @@
+use std::time::{Duration, Instant};
+
+use advent2022::rng::Rng;
+
+use super::{
+    crane::{self, CrateMover9000, CrateMover9001},
+    engine, Move, Stacks,
+};
+
+// run times the engine on generated stacks and moves, next to the same moves
+// made lift by lift through the Crane trait, and checks that both agree
+pub fn run(args: &[String]) -> Result<(), String> {
+    let (mut stack_count, mut crates, mut moves, mut max_amount, mut seed) =
+        (9, 100_000, 200_000, 1000, 0);
+    let mut rest = args.iter();
+    while let Some(arg) = rest.next() {
+        let target = match arg.as_str() {
+            "--stacks" => &mut stack_count,
+            "--crates" => &mut crates,
+            "--moves" => &mut moves,
+            "--max-amount" => &mut max_amount,
+            "--seed" => &mut seed,
+            _ => return Err(format!("unexpected argument `{}`", arg)),
+        };
+        let value = rest
+            .next()
+            .ok_or_else(|| format!("missing value for {}", arg))?;
+        *target = value
+            .parse()
+            .map_err(|_| format!("invalid value `{}` for {}", value, arg))?;
+    }
+    if stack_count < 2 || crates == 0 || max_amount == 0 {
+        return Err("moves need at least 2 stacks, a crate and an amount of 1".to_string());
+    }
+
+    let mut rng = Rng::new(seed as u64);
+    let stacks = generate_stacks(&mut rng, stack_count, crates);
+    let moves = generate_moves(&mut rng, &stacks, moves, max_amount);
+    let moved: usize = moves.iter().map(|mv| mv.amount).sum();
+    println!(
+        "{} moves of {} crates in all, between {} stacks of {} crates",
+        moves.len(),
+        moved,
+        stack_count,
+        crates
+    );
+
+    let (engine_1, time_1) = time(|| engine::run_9000(&stacks, &moves))?;
+    let (engine_2, time_2) = time(|| engine::run_9001(&stacks, &moves))?;
+    report("engine 9000", &engine_1, time_1, moved);
+    report("engine 9001", &engine_2, time_2, moved);
+
+    let (crane_1, time_1) = time(|| crane::run(&mut CrateMover9000, &stacks, &moves))?;
+    let (crane_2, time_2) = time(|| crane::run(&mut CrateMover9001, &stacks, &moves))?;
+    report("crane  9000", &crane_1, time_1, moved);
+    report("crane  9001", &crane_2, time_2, moved);
+
+    if (engine_1, engine_2) != (crane_1, crane_2) {
+        return Err("the engine and the cranes end up with different stacks".to_string());
+    }
+    Ok(())
+}
+
+fn time(f: impl Fn() -> Result<Stacks, String>) -> Result<(Stacks, Duration), String> {
+    let started = Instant::now();
+    let stacks = f()?;
+    Ok((stacks, started.elapsed()))
+}
+
+fn report(name: &str, stacks: &Stacks, time: Duration, moved: usize) {
+    let tops = crane::tops(stacks);
+    let tops = match tops.char_indices().nth(20) {
+        Some((i, _)) => format!("{}...", &tops[..i]),
+        None => tops,
+    };
+    println!(
+        "{} - {:>23} in {:>10.2?} ({:.0} crates/s)",
+        name,
+        tops,
+        time,
+        moved as f64 / time.as_secs_f64()
+    );
+}
+
+// generate_stacks spreads random crates over the stacks
+fn generate_stacks(rng: &mut Rng, stack_count: usize, crates: usize) -> Stacks {
+    let mut stacks: Stacks = vec![Vec::new(); stack_count];
+    for _ in 0..crates {
+        stacks[rng.below(stack_count)].push((b'A' + rng.below(26) as u8) as char);
+    }
+    stacks
+}
+
+// generate_moves makes moves that never take more crates than a stack has,
+// which only depends on how many crates every stack has, not on the crane
+fn generate_moves(rng: &mut Rng, stacks: &Stacks, count: usize, max_amount: usize) -> Vec<Move> {
+    let mut sizes: Vec<usize> = stacks.iter().map(|s| s.len()).collect();
+    let mut moves = Vec::with_capacity(count);
+    while moves.len() < count {
+        let from = rng.below(sizes.len());
+        if sizes[from] == 0 {
+            continue;
+        }
+        let to = (from + 1 + rng.below(sizes.len() - 1)) % sizes.len();
+        let amount = rng.range(1, sizes[from].min(max_amount) as i64) as usize;
+
+        sizes[from] -= amount;
+        sizes[to] += amount;
+        moves.push(Move { amount, from, to });
+    }
+    moves
+}
@@ -0,0 +1,203 @@
+use std::fmt;
+
+use super::{Move, Stacks};
+
+// Lift is one trip of a crane: it takes crates off the top of a stack and sets them
+// down on the other one, in the same order or upside down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lift {
+    pub crates: usize,
+    pub reversed: bool,
+}
+
+// Crane decides how a move is carried out, the moves themselves are checked before
+// a crane gets them, so a crane never sees a move that takes more crates than a stack has
+pub trait Crane: fmt::Display {
+    // lifts splits moving `amount` crates into trips, which together move exactly `amount`
+    fn lifts(&mut self, amount: usize) -> Vec<Lift>;
+}
+
+// CrateMover9000 moves one crate at a time
+pub struct CrateMover9000;
+
+impl Crane for CrateMover9000 {
+    fn lifts(&mut self, amount: usize) -> Vec<Lift> {
+        vec![
+            Lift {
+                crates: 1,
+                reversed: false,
+            };
+            amount
+        ]
+    }
+}
+
+impl fmt::Display for CrateMover9000 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CrateMover 9000")
+    }
+}
+
+// CrateMover9001 moves all crates of a move at once
+pub struct CrateMover9001;
+
+impl Crane for CrateMover9001 {
+    fn lifts(&mut self, amount: usize) -> Vec<Lift> {
+        vec![Lift {
+            crates: amount,
+            reversed: false,
+        }]
+    }
+}
+
+impl fmt::Display for CrateMover9001 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "CrateMover 9001")
+    }
+}
+
+// Custom is a crane that can only lift so many crates at once, and optionally
+// sets every other lift down upside down, counting lifts over all moves
+pub struct Custom {
+    pub capacity: Option<usize>,
+    pub reverse_every_other: bool,
+    lifts: usize,
+}
+
+impl Custom {
+    // parse reads a crane like `capacity=3`, `reverse-every-other` or both separated by a comma
+    pub fn parse(spec: &str) -> Result<Custom, String> {
+        let mut crane = Custom {
+            capacity: None,
+            reverse_every_other: false,
+            lifts: 0,
+        };
+
+        for option in spec.split(',') {
+            match option.split_once('=') {
+                Some(("capacity", capacity)) => {
+                    let capacity = capacity
+                        .parse()
+                        .ok()
+                        .filter(|c| *c > 0)
+                        .ok_or_else(|| format!("invalid capacity `{}`", capacity))?;
+                    crane.capacity = Some(capacity);
+                }
+                None if option == "reverse-every-other" => crane.reverse_every_other = true,
+                _ => {
+                    return Err(format!(
+                        "unknown crane option `{}`, use capacity=N or reverse-every-other",
+                        option
+                    ))
+                }
+            }
+        }
+
+        Ok(crane)
+    }
+}
+
+impl Crane for Custom {
+    fn lifts(&mut self, amount: usize) -> Vec<Lift> {
+        let capacity = self.capacity.unwrap_or(amount).max(1);
+        let mut lifts = Vec::new();
+        let mut left = amount;
+        while left > 0 {
+            let crates = left.min(capacity);
+            lifts.push(Lift {
+                crates,
+                reversed: self.reverse_every_other && self.lifts % 2 == 1,
+            });
+            self.lifts += 1;
+            left -= crates;
+        }
+        lifts
+    }
+}
+
+impl fmt::Display for Custom {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "crane")?;
+        if let Some(capacity) = self.capacity {
+            write!(f, " lifting {} at most", capacity)?;
+        }
+        if self.reverse_every_other {
+            write!(f, " reversing every other lift")?;
+        }
+        Ok(())
+    }
+}
+
+// crane_from_name picks one of the two CrateMovers or builds a custom crane
+pub fn crane_from_name(name: &str) -> Result<Box<dyn Crane>, String> {
+    match name {
+        "9000" => Ok(Box::new(CrateMover9000)),
+        "9001" => Ok(Box::new(CrateMover9001)),
+        spec => Ok(Box::new(Custom::parse(spec)?)),
+    }
+}
+
+// apply checks a move against the stacks and lets the crane carry it out, returning the lifts
+// it made. `number` is the position of the move in the list, counting from 1
+pub fn apply(
+    crane: &mut dyn Crane,
+    stacks: &mut Stacks,
+    mv: &Move,
+    number: usize,
+) -> Result<Vec<Lift>, String> {
+    let available = stacks[mv.from].len();
+    if available < mv.amount {
+        return Err(format!(
+            "move {} takes {} crates from stack {}, which only has {}",
+            number,
+            mv.amount,
+            mv.from + 1,
+            available
+        ));
+    }
+
+    let lifts = crane.lifts(mv.amount);
+    carry(stacks, mv, &lifts);
+    Ok(lifts)
+}
+
+// carry makes the lifts of a move that was already checked
+pub fn carry(stacks: &mut Stacks, mv: &Move, lifts: &[Lift]) {
+    for lift in lifts {
+        let from = &mut stacks[mv.from];
+        let mut crates = from.split_off(from.len() - lift.crates);
+        if lift.reversed {
+            crates.reverse();
+        }
+        stacks[mv.to].extend(crates);
+    }
+}
+
+// uncarry undoes the lifts of a move, last lift first, putting every crate back where it was
+pub fn uncarry(stacks: &mut Stacks, mv: &Move, lifts: &[Lift]) {
+    for lift in lifts.iter().rev() {
+        let to = &mut stacks[mv.to];
+        let mut crates = to.split_off(to.len() - lift.crates);
+        if lift.reversed {
+            crates.reverse();
+        }
+        stacks[mv.from].extend(crates);
+    }
+}
+
+// run plays all moves on a copy of the stacks
+pub fn run(crane: &mut dyn Crane, stacks: &Stacks, moves: &[Move]) -> Result<Stacks, String> {
+    let mut stacks = stacks.clone();
+    for (i, mv) in moves.iter().enumerate() {
+        apply(crane, &mut stacks, mv, i + 1)?;
+    }
+    Ok(stacks)
+}
+
+// tops reads the crate on top of every stack, with a space for an empty stack
+pub fn tops(stacks: &Stacks) -> String {
+    stacks
+        .iter()
+        .map(|stack| stack.last().copied().unwrap_or(' '))
+        .collect()
+}
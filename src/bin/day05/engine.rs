@@ -0,0 +1,55 @@
+use super::{Move, Stacks};
+
+// The engine runs the two CrateMovers without going through the Crane trait. A move is a single
+// drain off the top of one stack into the other, and for the CrateMover 9000 the drain is read
+// backwards rather than moving the crates one at a time, so a move costs one copy of its crates
+
+pub fn run_9000(stacks: &Stacks, moves: &[Move]) -> Result<Stacks, String> {
+    run(stacks, moves, true)
+}
+
+pub fn run_9001(stacks: &Stacks, moves: &[Move]) -> Result<Stacks, String> {
+    run(stacks, moves, false)
+}
+
+fn run(stacks: &Stacks, moves: &[Move], reversed: bool) -> Result<Stacks, String> {
+    let mut stacks = stacks.clone();
+    for (i, mv) in moves.iter().enumerate() {
+        let available = stacks[mv.from].len();
+        if available < mv.amount {
+            return Err(format!(
+                "move {} takes {} crates from stack {}, which only has {}",
+                i + 1,
+                mv.amount,
+                mv.from + 1,
+                available
+            ));
+        }
+        let start = available - mv.amount;
+
+        // a crate taken off a stack and set down on the same stack stays where it was,
+        // for either crane
+        if mv.from == mv.to {
+            continue;
+        }
+
+        let (from, to) = pair(&mut stacks, mv.from, mv.to);
+        if reversed {
+            to.extend(from.drain(start..).rev());
+        } else {
+            to.extend(from.drain(start..));
+        }
+    }
+    Ok(stacks)
+}
+
+// pair borrows two different stacks at once
+fn pair(stacks: &mut Stacks, a: usize, b: usize) -> (&mut Vec<char>, &mut Vec<char>) {
+    if a < b {
+        let (left, right) = stacks.split_at_mut(b);
+        (&mut left[a], &mut right[0])
+    } else {
+        let (left, right) = stacks.split_at_mut(a);
+        (&mut right[0], &mut left[b])
+    }
+}
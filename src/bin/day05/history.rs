@@ -0,0 +1,128 @@
+use super::{crane, crane::Crane, crane::Lift, Move, Stacks};
+
+// History walks through the moves one at a time and can go back again,
+// step 0 is the drawing and step n is the state after the first n moves
+pub struct History<'a> {
+    crane: Box<dyn Crane>,
+    moves: &'a [Move],
+    stacks: Stacks,
+    // the lifts of every move made so far, kept when stepping back so a step forward
+    // again repeats them, even for a crane that changes what it does over time
+    lifts: Vec<Vec<Lift>>,
+    step: usize,
+}
+
+impl<'a> History<'a> {
+    pub fn new(crane: Box<dyn Crane>, stacks: Stacks, moves: &'a [Move]) -> History<'a> {
+        History {
+            crane,
+            moves,
+            stacks,
+            lifts: Vec::new(),
+            step: 0,
+        }
+    }
+
+    pub fn crane(&self) -> &dyn Crane {
+        self.crane.as_ref()
+    }
+
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    // len is the number of moves, so also the last step
+    pub fn len(&self) -> usize {
+        self.moves.len()
+    }
+
+    pub fn stacks(&self) -> &Stacks {
+        &self.stacks
+    }
+
+    // last_move is the move that led to the current step
+    pub fn last_move(&self) -> Option<&Move> {
+        self.step.checked_sub(1).map(|i| &self.moves[i])
+    }
+
+    // forward makes the next move, false means there are no moves left
+    pub fn forward(&mut self) -> Result<bool, String> {
+        let Some(mv) = self.moves.get(self.step) else {
+            return Ok(false);
+        };
+
+        match self.lifts.get(self.step) {
+            Some(lifts) => crane::carry(&mut self.stacks, mv, lifts),
+            None => {
+                let lifts = crane::apply(self.crane.as_mut(), &mut self.stacks, mv, self.step + 1)?;
+                self.lifts.push(lifts);
+            }
+        }
+        self.step += 1;
+        Ok(true)
+    }
+
+    // back undoes the last move, false means we are back at the drawing
+    pub fn back(&mut self) -> bool {
+        let Some(mv) = self.last_move() else {
+            return false;
+        };
+        let mv = *mv;
+
+        self.step -= 1;
+        crane::uncarry(&mut self.stacks, &mv, &self.lifts[self.step]);
+        true
+    }
+
+    // jump goes forward or back to any step, stopping at the move that fails if there is one
+    pub fn jump(&mut self, step: usize) -> Result<(), String> {
+        if step > self.len() {
+            return Err(format!(
+                "there are only {} moves, pick a step from 0 to {}",
+                self.len(),
+                self.len()
+            ));
+        }
+
+        while self.step > step {
+            self.back();
+        }
+        while self.step < step {
+            self.forward()?;
+        }
+        Ok(())
+    }
+
+    // at is the state at some step, without moving away from the current one
+    pub fn at(&mut self, step: usize) -> Result<Stacks, String> {
+        let here = self.step;
+        let result = self.jump(step).map(|_| self.stacks.clone());
+        self.jump(here)?;
+        result
+    }
+}
+
+// diff lists the stacks that differ between two states, with the crates that stayed put,
+// the crates that left and the crates that came in
+pub fn diff(before: &Stacks, after: &Stacks) -> Vec<String> {
+    before
+        .iter()
+        .zip(after)
+        .enumerate()
+        .filter(|(_, (before, after))| before != after)
+        .map(|(i, (before, after))| {
+            let kept = before.iter().zip(after).take_while(|(a, b)| a == b).count();
+            let mut line = format!("stack {}:", i + 1);
+            for (sign, crates) in [
+                ("", &before[..kept]),
+                ("-", &before[kept..]),
+                ("+", &after[kept..]),
+            ] {
+                if !crates.is_empty() {
+                    line += &format!(" {}{}", sign, crates.iter().collect::<String>());
+                }
+            }
+            line
+        })
+        .collect()
+}
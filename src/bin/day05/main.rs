@@ -1,116 +1,336 @@
-use std::{fs, str::Chars, vec};
+use std::{env, fmt, fs};
 
+use advent2022::{fuzz, input_path, prompt};
+
+use crane::CrateMover9000;
+use history::History;
+
+mod bench;
+mod crane;
+mod engine;
+mod history;
+mod repl;
+mod reverse;
+
+// the crates on every stack, from the bottom up
+type Stacks = Vec<Vec<char>>;
+
+#[derive(Debug, Clone, Copy)]
 struct Move {
     amount: usize,
     from: usize,
     to: usize,
 }
 
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "move {} from {} to {}",
+            self.amount,
+            self.from + 1,
+            self.to + 1
+        )
+    }
+}
+
 fn main() {
-    let moves = parse_moves();
+    fuzz::exit_if_requested("day05", parse_input);
+
+    // `day05 bench [--stacks N] [--crates N] [--moves N] [--max-amount N] [--seed N]`
+    // times generated moves instead of solving the input
+    let mut args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|a| a == "bench") {
+        bench::run(&args[1..]).unwrap_or_else(|e| panic!("{}", e));
+        return;
+    }
+
+    let input = fs::read_to_string(input_path("day05")).expect("unable to read file");
+    let (stacks, moves) = parse_input(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
+
+    // `day05 --crane <crane>` runs the moves with another crane, like `9001`,
+    // `capacity=3` or `capacity=3,reverse-every-other`. `day05 show <step>` draws the stacks
+    // after that many moves, `day05 diff <step> <step>` compares two steps and
+    // `day05 repl` steps through the moves, all with the CrateMover 9000 unless a crane is picked.
+    // `day05 reverse [drawing]` finds the stacks both CrateMovers, or the crane picked,
+    // would have started from to end up at the drawing, or where the moves end up by default
+    let crane_name = args.iter().position(|a| a == "--crane").map(|i| {
+        let name = args
+            .get(i + 1)
+            .cloned()
+            .expect("usage: day05 --crane <crane>");
+        args.drain(i..i + 2);
+        name
+    });
+    let new_crane = |name: &str| crane::crane_from_name(name).unwrap_or_else(|e| panic!("{}", e));
+    let crane = crane_name.as_deref().map(new_crane);
+    let step = |i: usize| -> usize {
+        args.get(i)
+            .and_then(|step| step.parse().ok())
+            .filter(|step| *step <= moves.len())
+            .unwrap_or_else(|| {
+                panic!(
+                    "usage: day05 show <step> | day05 diff <step> <step>, with a step from 0 to {}",
+                    moves.len()
+                )
+            })
+    };
+
+    let mode = args.first().map(|s| s.as_str());
+    if matches!(mode, Some("show" | "diff")) || prompt::requested() {
+        let crane = crane.unwrap_or_else(|| Box::new(CrateMover9000));
+        let mut history = History::new(crane, stacks, &moves);
+        let at = |history: &mut History, step| {
+            history
+                .at(step)
+                .unwrap_or_else(|e| panic!("invalid input: {}", e))
+        };
+        match mode {
+            Some("show") => println!("{}", draw(&at(&mut history, step(1)))),
+            Some("diff") => {
+                let (before, after) = (at(&mut history, step(1)), at(&mut history, step(2)));
+                let changes = history::diff(&before, &after);
+                if changes.is_empty() {
+                    println!("steps {} and {} are the same", step(1), step(2));
+                }
+                for line in changes {
+                    println!("{}", line);
+                }
+            }
+            _ => repl::run(history),
+        }
+        return;
+    }
+
+    if mode == Some("reverse") {
+        let last = args.get(1).map(|path| {
+            let drawing = fs::read_to_string(path).expect("unable to read the drawing");
+            parse_stacks(drawing.trim_end_matches('\n'))
+                .unwrap_or_else(|e| panic!("invalid drawing: {}", e))
+        });
+        let names = match &crane_name {
+            Some(name) => vec![name.as_str()],
+            None => vec!["9000", "9001"],
+        };
+
+        for name in names {
+            let mut crane = new_crane(name);
+            let last = match &last {
+                Some(last) => last.clone(),
+                None => crane::run(crane.as_mut(), &stacks, &moves)
+                    .unwrap_or_else(|e| panic!("invalid input: {}", e)),
+            };
+            // a crane can change what it does as it goes, so the reverse run gets a fresh one
+            let first = match reverse::unrun(new_crane(name).as_mut(), &last, &moves) {
+                Ok(first) => first,
+                Err(e) => {
+                    println!("the {} can't end up at the drawing: {}\n", crane, e);
+                    continue;
+                }
+            };
+
+            println!("the {} starts from", crane);
+            println!("{}", draw(&first));
+            let changes = history::diff(&stacks, &first);
+            if first.len() != stacks.len() {
+                println!(
+                    "which has {} stacks, not {} like the input\n",
+                    first.len(),
+                    stacks.len()
+                );
+            } else if changes.is_empty() {
+                println!("which is the drawing in the input\n");
+            } else {
+                println!("which differs from the drawing in the input:");
+                for line in changes {
+                    println!("{}", line);
+                }
+                println!();
+            }
+        }
+        return;
+    }
 
-    println!("Part 1 - {}", part_1(&moves));
-    println!("Part 2 - {}", part_2(&moves));
+    if let Some(mut crane) = crane {
+        let stacks = crane::run(crane.as_mut(), &stacks, &moves)
+            .unwrap_or_else(|e| panic!("invalid input: {}", e));
+        println!("{} - {}", crane, crane::tops(&stacks));
+        return;
+    }
+
+    let invalid = |e| panic!("invalid input: {}", e);
+    println!(
+        "Part 1 - {}",
+        part_1(&stacks, &moves).unwrap_or_else(invalid)
+    );
+    println!(
+        "Part 2 - {}",
+        part_2(&stacks, &moves).unwrap_or_else(invalid)
+    );
 }
 
-fn parse_moves() -> Vec<Move> {
-    let input = fs::read_to_string("input/day05.txt").expect("unable to read file");
+// parse_input reads the drawing of the stacks, a blank line and then the moves
+fn parse_input(input: &str) -> Result<(Stacks, Vec<Move>), String> {
+    let (drawing, moves) = input
+        .split_once("\n\n")
+        .ok_or_else(|| "expected a blank line after the drawing".to_string())?;
+    let stacks = parse_stacks(drawing)?;
 
-    input
+    // the moves start two lines below the last line of the drawing
+    let first_line = drawing.split('\n').count() + 2;
+    let moves = moves
         .split("\n")
-        .skip(10)
-        .map(|line| {
-            let mut mv = Move {
-                amount: 0,
-                from: 0,
-                to: 0,
-            };
-            let mut ch = line.chars();
-            skip(&mut ch, 5);
-            mv.amount = parse_number(&mut ch);
-            skip(&mut ch, 5);
-            mv.from = parse_number(&mut ch) - 1;
-            skip(&mut ch, 3);
-            mv.to = parse_number(&mut ch) - 1;
-            mv
+        .enumerate()
+        .map(|(i, line)| {
+            parse_move(line, stacks.len()).map_err(|e| format!("line {}: {}", first_line + i, e))
         })
-        .collect()
+        .collect::<Result<_, _>>()?;
+
+    Ok((stacks, moves))
 }
 
-fn skip(ch: &mut Chars, n: i64) {
-    for _ in 0..n {
-        ch.next().expect("expected more characters");
+// parse_stacks reads a drawing of any size, the last line numbers the stacks from 1
+// and every crate has to sit right above one of those numbers
+fn parse_stacks(drawing: &str) -> Result<Stacks, String> {
+    let mut lines: Vec<&str> = drawing.split('\n').collect();
+    let index_line = lines.pop().unwrap_or_default();
+
+    // the columns every stack number takes up
+    let mut columns: Vec<(usize, usize)> = Vec::new();
+    let chars: Vec<char> = index_line.chars().collect();
+    let mut i = 0;
+    while i < chars.len() {
+        if chars[i] == ' ' {
+            i += 1;
+            continue;
+        }
+        let start = i;
+        while i < chars.len() && chars[i] != ' ' {
+            i += 1;
+        }
+        let label: String = chars[start..i].iter().collect();
+        if label.parse::<usize>().ok() != Some(columns.len() + 1) {
+            return Err(format!(
+                "line {}: expected stack number {}, found `{}`",
+                lines.len() + 1,
+                columns.len() + 1,
+                label
+            ));
+        }
+        columns.push((start, i - 1));
+    }
+    if columns.is_empty() {
+        return Err(format!("line {}: no stack numbers", lines.len() + 1));
     }
-}
 
-fn input_hardcoded() -> Vec<Vec<char>> {
-    vec![
-        vec!['V', 'R', 'H', 'B', 'G', 'D', 'W'],
-        vec!['F', 'R', 'C', 'G', 'N', 'J'],
-        vec!['J', 'N', 'D', 'H', 'F', 'S', 'L'],
-        vec!['V', 'S', 'D', 'J'],
-        vec!['V', 'N', 'W', 'Q', 'R', 'D', 'H', 'S'],
-        vec!['M', 'C', 'H', 'G', 'P'],
-        vec!['C', 'H', 'Z', 'L', 'G', 'B', 'J', 'F'],
-        vec!['R', 'J', 'S'],
-        vec!['M', 'V', 'N', 'B', 'R', 'S', 'G', 'L'],
-    ]
-    .into_iter()
-    .map(|mut v| {
-        v.reverse();
-        v
-    })
-    .collect()
+    let mut stacks: Stacks = vec![Vec::new(); columns.len()];
+    for (row, line) in lines.iter().enumerate().rev() {
+        let line_number = row + 1;
+        let chars: Vec<char> = line.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            match chars[i..] {
+                [' ', ..] => i += 1,
+                ['[', name, ']', ..] if name != ' ' => {
+                    // a crate drawn as [X] belongs to the stack number below any of its columns
+                    let stack = columns
+                        .iter()
+                        .position(|(start, end)| *start <= i + 2 && i <= *end)
+                        .ok_or_else(|| {
+                            format!(
+                                "line {}: crate [{}] isn't above a stack number",
+                                line_number, name
+                            )
+                        })?;
+                    if stacks[stack].len() != lines.len() - 1 - row {
+                        return Err(format!(
+                            "line {}: crate [{}] floats above stack {}",
+                            line_number,
+                            name,
+                            stack + 1
+                        ));
+                    }
+                    stacks[stack].push(name);
+                    i += 3;
+                }
+                _ => {
+                    return Err(format!(
+                        "line {}: unexpected `{}` in the drawing",
+                        line_number, chars[i]
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(stacks)
 }
 
-fn part_1(moves: &Vec<Move>) -> String {
-    let mut state = input_hardcoded();
+// parse_move reads `move 3 from 1 to 2`, and turns the stack numbers into indices
+fn parse_move(line: &str, stack_count: usize) -> Result<Move, String> {
+    let words: Vec<&str> = line.split(' ').collect();
+    let ["move", amount, "from", from, "to", to] = words[..] else {
+        return Err(format!("expected `move N from A to B`, found `{}`", line));
+    };
 
-    moves.iter().for_each(|mv| {
-        for _ in 0..mv.amount {
-            let ch = state[mv.from]
-                .pop()
-                .expect("attempt to take from empty pile");
-            state[mv.to].push(ch);
+    let amount = parse_number(amount)?;
+    let stack = |number: &str| -> Result<usize, String> {
+        let stack = parse_number(number)?;
+        if !(1..=stack_count).contains(&stack) {
+            return Err(format!(
+                "there is no stack {}, only 1 to {}",
+                stack, stack_count
+            ));
         }
-    });
+        Ok(stack - 1)
+    };
 
-    state.iter().map(|pile| pile.last().unwrap()).collect()
+    Ok(Move {
+        amount,
+        from: stack(from)?,
+        to: stack(to)?,
+    })
 }
 
-fn part_2(moves: &Vec<Move>) -> String {
-    let mut state = input_hardcoded();
+fn part_1(stacks: &Stacks, moves: &[Move]) -> Result<String, String> {
+    engine::run_9000(stacks, moves).map(|stacks| crane::tops(&stacks))
+}
 
-    moves.iter().for_each(|mv| {
-        for i in 0..mv.amount {
-            let ch = state[mv.from][state[mv.from].len() - mv.amount + i];
-            state[mv.to].push(ch);
-        }
-        let i = state[mv.from].len() - mv.amount;
-        state[mv.from].truncate(i);
-    });
+fn part_2(stacks: &Stacks, moves: &[Move]) -> Result<String, String> {
+    engine::run_9001(stacks, moves).map(|stacks| crane::tops(&stacks))
+}
 
-    state.iter().map(|pile| pile.last().unwrap()).collect()
+// draw draws the stacks the way the puzzle does, with the stack numbers on the last line
+fn draw(stacks: &Stacks) -> String {
+    let height = stacks.iter().map(|stack| stack.len()).max().unwrap_or(0);
+    let mut lines: Vec<String> = (0..height)
+        .rev()
+        .map(|row| {
+            let line: Vec<String> = stacks
+                .iter()
+                .map(|stack| match stack.get(row) {
+                    Some(name) => format!("[{}]", name),
+                    None => "   ".to_string(),
+                })
+                .collect();
+            line.join(" ").trim_end().to_string()
+        })
+        .collect();
+    lines.push(
+        (1..=stacks.len())
+            .map(|i| format!(" {:<3}", i))
+            .collect::<String>()
+            .trim_end()
+            .to_string(),
+    );
+    lines.join("\n")
 }
 
-fn parse_number(stream: &mut Chars) -> usize {
-    let mut chars = Vec::new();
-    loop {
-        match stream.next() {
-            Some(c) => {
-                if c.is_numeric() {
-                    chars.push(c)
-                } else {
-                    break;
-                }
-            }
-            None => break,
-        }
+fn parse_number(number: &str) -> Result<usize, String> {
+    if !number.chars().all(|c| c.is_ascii_digit()) {
+        return Err(format!("could not parse int `{}`", number));
     }
-
-    chars
-        .iter()
-        .collect::<String>()
+    number
         .parse()
-        .expect("could not parse int")
+        .map_err(|_| format!("could not parse int `{}`", number))
 }
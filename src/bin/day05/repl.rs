@@ -0,0 +1,141 @@
+use std::cell::RefCell;
+
+use advent2022::prompt::{self, Command};
+
+use super::{draw, history, history::History};
+
+pub fn run(history: History) {
+    println!(
+        "{} moves with the {}, at {}",
+        history.len(),
+        history.crane(),
+        position(&history)
+    );
+    prompt::run(
+        "day05",
+        &RefCell::new(history),
+        &[
+            Command {
+                name: "next",
+                args: "[n]",
+                help: "make the next move, or the next n",
+                run: next,
+                complete: None,
+            },
+            Command {
+                name: "back",
+                args: "[n]",
+                help: "undo the last move, or the last n",
+                run: back,
+                complete: None,
+            },
+            Command {
+                name: "jump",
+                args: "<step>",
+                help: "go to the state after that many moves",
+                run: jump,
+                complete: Some(|history| vec!["0".to_string(), history.borrow().len().to_string()]),
+            },
+            Command {
+                name: "show",
+                args: "[step]",
+                help: "draw the stacks, now or at another step",
+                run: show,
+                complete: None,
+            },
+            Command {
+                name: "diff",
+                args: "<step> [step]",
+                help: "the stacks that changed between two steps, or between a step and now",
+                run: diff,
+                complete: None,
+            },
+        ],
+    );
+}
+
+// count reads an optional number of steps, 1 when it is left out
+fn count(args: &[&str]) -> Result<usize, String> {
+    match args.first() {
+        None => Ok(1),
+        Some(_) => prompt::parse_arg(args, 0, "n"),
+    }
+}
+
+// position describes the current step and the move that led to it
+fn position(history: &History) -> String {
+    match history.last_move() {
+        Some(mv) => format!("step {}/{} after {}", history.step(), history.len(), mv),
+        None => format!("step 0/{}, the drawing", history.len()),
+    }
+}
+
+fn next(history: &RefCell<History>, args: &[&str]) -> Result<String, String> {
+    let mut history = history.borrow_mut();
+    for _ in 0..count(args)? {
+        if !history.forward()? {
+            break;
+        }
+    }
+    Ok(format!(
+        "{}\n{}",
+        position(&history),
+        draw(history.stacks())
+    ))
+}
+
+fn back(history: &RefCell<History>, args: &[&str]) -> Result<String, String> {
+    let mut history = history.borrow_mut();
+    for _ in 0..count(args)? {
+        if !history.back() {
+            break;
+        }
+    }
+    Ok(format!(
+        "{}\n{}",
+        position(&history),
+        draw(history.stacks())
+    ))
+}
+
+fn jump(history: &RefCell<History>, args: &[&str]) -> Result<String, String> {
+    let mut history = history.borrow_mut();
+    history
+        .jump(prompt::parse_arg(args, 0, "step")?)
+        .map_err(|e| format!("{}, stopped at step {}", e, history.step()))?;
+    Ok(format!(
+        "{}\n{}",
+        position(&history),
+        draw(history.stacks())
+    ))
+}
+
+fn show(history: &RefCell<History>, args: &[&str]) -> Result<String, String> {
+    let mut history = history.borrow_mut();
+    match args.first() {
+        None => Ok(format!(
+            "{}\n{}",
+            position(&history),
+            draw(history.stacks())
+        )),
+        Some(_) => {
+            let step = prompt::parse_arg(args, 0, "step")?;
+            Ok(draw(&history.at(step)?))
+        }
+    }
+}
+
+fn diff(history: &RefCell<History>, args: &[&str]) -> Result<String, String> {
+    let mut history = history.borrow_mut();
+    let from = prompt::parse_arg(args, 0, "step")?;
+    let to = match args.get(1) {
+        None => history.step(),
+        Some(_) => prompt::parse_arg(args, 1, "step")?,
+    };
+
+    let changes = history::diff(&history.at(from)?, &history.at(to)?);
+    if changes.is_empty() {
+        return Ok(format!("steps {} and {} are the same", from, to));
+    }
+    Ok(changes.join("\n"))
+}
@@ -0,0 +1,38 @@
+use super::{crane, crane::Crane, Move, Stacks};
+
+// unrun plays the moves backwards from the stacks at the end, giving the stacks the crane started with.
+// A crane decides its lifts from the amounts alone, so the lifts are worked out going forward first,
+// then every move is undone last move first
+pub fn unrun(crane: &mut dyn Crane, last: &Stacks, moves: &[Move]) -> Result<Stacks, String> {
+    if let Some((i, mv)) = moves
+        .iter()
+        .enumerate()
+        .find(|(_, mv)| mv.from.max(mv.to) >= last.len())
+    {
+        return Err(format!(
+            "move {} ({}) needs more than the {} stacks at the end",
+            i + 1,
+            mv,
+            last.len()
+        ));
+    }
+
+    let lifts: Vec<_> = moves.iter().map(|mv| crane.lifts(mv.amount)).collect();
+    let mut stacks = last.clone();
+    for (i, (mv, lifts)) in moves.iter().zip(&lifts).enumerate().rev() {
+        // the crates a move set down have to still be on top when it is undone
+        let available = stacks[mv.to].len();
+        if available < mv.amount {
+            return Err(format!(
+                "move {} ({}) can't be undone, stack {} only has {} crates at that point",
+                i + 1,
+                mv,
+                mv.to + 1,
+                available
+            ));
+        }
+        crane::uncarry(&mut stacks, mv, lifts);
+    }
+
+    Ok(stacks)
+}
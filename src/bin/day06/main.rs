@@ -1,38 +1,186 @@
-use std::fs;
+use std::{
+    collections::VecDeque,
+    env,
+    fs::File,
+    io::{self, BufReader, Read},
+};
+
+use advent2022::{fuzz, input_path};
+
+mod markers;
 
 fn main() {
-    let input = fs::read_to_string("input/day06.txt").expect("unable to read file");
+    fuzz::exit_if_requested("day06", |input: &str| {
+        find_markers(input.as_bytes(), &[4, 14])
+    });
+
+    // `day06 scan [length]` looks for a marker in whatever is piped in, 4 bytes long by default
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first().is_some_and(|a| a == "scan") {
+        let marker_len = args
+            .get(1)
+            .map_or(Some(4), |len| len.parse().ok().filter(|len| *len > 0))
+            .unwrap_or_else(|| panic!("usage: day06 scan [length]"));
+        let marker = find_marker(io::stdin().lock(), marker_len)
+            .unwrap_or_else(|e| panic!("invalid input: {}", e));
+        println!("Marker of {} - {}", marker_len, describe(marker));
+        return;
+    }
+
+    // `day06 markers [--len N]... [--first N | --all] [--stdin]` lists the markers of several
+    // lengths, 4 and 14 by default, from one pass over the input or whatever is piped in
+    if args.first().is_some_and(|a| a == "markers") {
+        let (mut lengths, mut keep, mut stdin) = (Vec::new(), Some(1), false);
+        let mut rest = args[1..].iter();
+        let number = |value: Option<&String>| {
+            value
+                .and_then(|n| n.parse::<usize>().ok())
+                .filter(|n| *n > 0)
+                .unwrap_or_else(|| {
+                    panic!("usage: day06 markers [--len N]... [--first N | --all] [--stdin]")
+                })
+        };
+        while let Some(arg) = rest.next() {
+            match arg.as_str() {
+                "--len" => lengths.push(number(rest.next())),
+                "--first" => keep = Some(number(rest.next())),
+                "--all" => keep = None,
+                "--stdin" => stdin = true,
+                _ => panic!("unexpected argument `{}`", arg),
+            }
+        }
+        if lengths.is_empty() {
+            lengths = vec![4, 14];
+        }
 
-    println!("Part 1 - {}", solve(&input, 4));
-    println!("Part 2 - {}", solve(&input, 14));
+        let scan = if stdin {
+            markers::scan(io::stdin().lock(), &lengths, keep)
+        } else {
+            let file = File::open(input_path("day06")).expect("unable to read file");
+            markers::scan(file, &lengths, keep)
+        };
+        print!(
+            "{}",
+            markers::render(&scan.unwrap_or_else(|e| panic!("invalid input: {}", e)))
+        );
+        return;
+    }
+
+    // both parts come out of one pass over the input, which stops at the second marker
+    let file = File::open(input_path("day06")).expect("unable to read file");
+    let found = find_markers(file, &[4, 14]).unwrap_or_else(|e| panic!("invalid input: {}", e));
+    println!("Part 1 - {}", describe(found[0]));
+    println!("Part 2 - {}", describe(found[1]));
+}
+
+fn describe(marker: Option<usize>) -> String {
+    marker.map_or("no marker".to_string(), |end| end.to_string())
 }
 
-fn solve(input: &String, marker_len: usize) -> i64 {
-    let mut history: Vec<char> = input.chars().take(marker_len).collect();
+// find_marker reads the datastream until the last `marker_len` bytes are all different and
+// returns how many bytes that took, or None when the stream ends first
+fn find_marker(reader: impl Read, marker_len: usize) -> Result<Option<usize>, String> {
+    Ok(find_markers(reader, &[marker_len])?[0])
+}
 
-    for (i, ch) in input.chars().enumerate().skip(marker_len) {
-        if unique(&history) {
-            return i as i64;
+// find_markers is find_marker for several lengths at once, it reads until all of them are found
+fn find_markers(reader: impl Read, lengths: &[usize]) -> Result<Vec<Option<usize>>, String> {
+    let mut markers = vec![None; lengths.len()];
+    read_stream(reader, |detector| {
+        for (marker, marker_len) in markers.iter_mut().zip(lengths) {
+            if marker.is_none() && detector.run_len() >= *marker_len {
+                *marker = Some(detector.position);
+            }
         }
+        markers.contains(&None)
+    })?;
+    Ok(markers)
+}
+
+// read_stream hands the detector to f after every byte, for as long as f returns true.
+// Every byte of the datastream counts, only a newline at the very end, `\n` or `\r\n`,
+// is left out. Line breaks are held back until it is clear whether more bytes follow
+fn read_stream(reader: impl Read, mut f: impl FnMut(&Detector) -> bool) -> Result<(), String> {
+    let mut detector = Detector::new();
+    let mut held: Vec<u8> = Vec::new();
+    let mut push = |detector: &mut Detector, byte: u8| {
+        detector.push(byte);
+        f(detector)
+    };
 
-        for i in 0..marker_len - 1 {
-            history[i] = history[i + 1];
+    for (i, byte) in BufReader::new(reader).bytes().enumerate() {
+        let byte = byte.map_err(|e| format!("byte {}: {}", i + 1, e))?;
+        if byte == b'\r' || byte == b'\n' {
+            held.push(byte);
+            continue;
+        }
+        for held_byte in held.drain(..) {
+            if !push(&mut detector, held_byte) {
+                return Ok(());
+            }
+        }
+        if !push(&mut detector, byte) {
+            return Ok(());
+        }
+    }
+
+    let trailing = match held.as_slice() {
+        [.., b'\r', b'\n'] => 2,
+        [.., b'\n'] => 1,
+        _ => 0,
+    };
+    for held_byte in &held[..held.len() - trailing] {
+        if !push(&mut detector, *held_byte) {
+            break;
         }
-        history[marker_len - 1] = ch;
     }
+    Ok(())
+}
 
-    panic!("No unique set found");
+// Detector keeps where every byte was last seen and the run of different bytes that ends at
+// the last one. Every window that fits in the run is a marker, so one detector serves every
+// marker length, and a byte costs the same whatever the lengths are
+struct Detector {
+    // one past the position a byte was last seen at, 0 for never
+    last_seen: [usize; 256],
+    // how many bytes were pushed
+    position: usize,
+    run: VecDeque<u8>,
 }
 
-fn unique(vals: &Vec<char>) -> bool {
-    let l = vals.len();
-    for i in 0..l - 1 {
-        for j in i + 1..l {
-            if vals[i] == vals[j] {
-                return false;
-            }
+impl Detector {
+    fn new() -> Detector {
+        Detector {
+            last_seen: [0; 256],
+            position: 0,
+            run: VecDeque::new(),
         }
     }
 
-    true
+    fn push(&mut self, byte: u8) {
+        // the run can't reach back past the last time this byte showed up,
+        // every byte joins and leaves the run once so this stays O(1) per byte on average
+        let run_start = self.position - self.run.len();
+        let seen = self.last_seen[byte as usize];
+        if seen > run_start {
+            self.run.drain(..seen - run_start);
+        }
+
+        self.position += 1;
+        self.last_seen[byte as usize] = self.position;
+        self.run.push_back(byte);
+    }
+
+    fn run_len(&self) -> usize {
+        self.run.len()
+    }
+
+    // window is the last len bytes of the run, which are all different, so len can't be
+    // more than run_len
+    fn window(&self, len: usize) -> String {
+        self.run
+            .range(self.run.len() - len..)
+            .map(|b| *b as char)
+            .collect()
+    }
 }
@@ -0,0 +1,98 @@
+use std::io::Read;
+
+use super::read_stream;
+
+// Markers are the markers found for one window length, as the byte count at the end
+// of the marker and the marker itself
+pub struct Markers {
+    pub marker_len: usize,
+    pub found: Vec<(usize, String)>,
+    // every marker counts, also those that weren't kept
+    pub total: usize,
+}
+
+// Scan is what one pass over the datastream finds
+pub struct Scan {
+    pub markers: Vec<Markers>,
+    // the longest run of different bytes, as the byte count at its end and the run itself
+    pub longest: Option<(usize, String)>,
+    pub length: usize,
+}
+
+// scan reads the whole datastream once, keeping the first `keep` markers of every length,
+// or all of them when keep is None
+pub fn scan(reader: impl Read, lengths: &[usize], keep: Option<usize>) -> Result<Scan, String> {
+    let mut markers: Vec<Markers> = lengths
+        .iter()
+        .map(|&marker_len| Markers {
+            marker_len,
+            found: Vec::new(),
+            total: 0,
+        })
+        .collect();
+    let mut longest: Option<(usize, String)> = None;
+    let mut length = 0;
+
+    read_stream(reader, |detector| {
+        length = detector.position;
+        // the run length tells whether there is a marker, only the kept ones get their bytes
+        for markers in markers.iter_mut() {
+            if detector.run_len() < markers.marker_len {
+                continue;
+            }
+            markers.total += 1;
+            if keep.is_none_or(|keep| markers.found.len() < keep) {
+                markers
+                    .found
+                    .push((detector.position, detector.window(markers.marker_len)));
+            }
+        }
+
+        // a run only holds different bytes, so it can't grow past 256 and this copies at most
+        // 256 times however long the datastream is
+        if longest
+            .as_ref()
+            .is_none_or(|(_, run)| detector.run_len() > run.len())
+        {
+            longest = Some((detector.position, detector.window(detector.run_len())));
+        }
+        true
+    })?;
+
+    Ok(Scan {
+        markers,
+        longest,
+        length,
+    })
+}
+
+// render lists the markers of every length with the byte count they end at, which is the
+// puzzle's answer for the first one, and the bytes they start at counting from 1
+pub fn render(scan: &Scan) -> String {
+    let mut out = String::new();
+    for markers in &scan.markers {
+        out += &format!("Length {} - {} markers", markers.marker_len, markers.total);
+        if markers.found.len() < markers.total {
+            out += &format!(", the first {}", markers.found.len());
+        }
+        out += "\n";
+        for (end, marker) in &markers.found {
+            out += &format!("{:>10} {:>10}  {}\n", end - marker.len() + 1, end, marker);
+        }
+    }
+
+    match &scan.longest {
+        Some((end, run)) => {
+            out += &format!(
+                "Longest run of different bytes - {} from {} to {} of {}: {}\n",
+                run.len(),
+                end - run.len() + 1,
+                end,
+                scan.length,
+                run
+            )
+        }
+        None => out += "Longest run of different bytes - the datastream is empty\n",
+    }
+    out
+}
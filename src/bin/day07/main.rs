@@ -1,142 +1,228 @@
-use std::{collections::HashMap, fs};
+use std::{env, fs, process};
+
+use advent2022::{fuzz, input_path, prompt};
+
+use vfs::{Dir, Entry, Fs};
+
+mod plan;
+mod repl;
+mod vfs;
+mod views;
 
 fn main() {
-    let input = fs::read_to_string("input/day07.txt").expect("unable to read file");
+    fuzz::exit_if_requested("day07", parse_input);
+
+    let input = fs::read_to_string(input_path("day07")).expect("unable to read file");
+
+    let filesystem = parse_input(&input).unwrap_or_else(|e| {
+        eprintln!("invalid input:\n{}", e);
+        process::exit(1);
+    });
+    for conflict in &filesystem.conflicts {
+        eprintln!("warning: {}", conflict);
+    }
+    let root_dir = filesystem.root;
+
+    // `day07 tree`, `day07 du` and `day07 json` show the filesystem instead of solving,
+    // each takes `-h` for sizes in K, M and G and `--depth N` to stop below that level
+    let args: Vec<String> = env::args().skip(1).collect();
+    if let Some(view) = args
+        .first()
+        .filter(|view| ["tree", "du", "json"].contains(&view.as_str()))
+    {
+        let options = views::Options::parse(&args[1..]).unwrap_or_else(|e| panic!("{}", e));
+        print!(
+            "{}",
+            match view.as_str() {
+                "tree" => views::tree(&root_dir, options),
+                "du" => views::du(&root_dir, options),
+                _ => views::json(&root_dir, options),
+            }
+        );
+        return;
+    }
+
+    if prompt::requested() {
+        repl::run(&root_dir);
+        return;
+    }
+
+    // `--disk N` and `--needed N` change the disk of part 2, 70000000 with 30000000 needed,
+    // `day07 plan [--files] [--alternatives N]` looks for the best set of directories to
+    // delete instead, or directories and files
+    let mut params = plan::Params {
+        disk: 70000000,
+        needed: 30000000,
+        files: false,
+        alternatives: 5,
+    };
+    let planning = args.first().is_some_and(|a| a == "plan");
+    let mut rest = args.iter().skip(usize::from(planning));
+    while let Some(arg) = rest.next() {
+        if planning && arg == "--files" {
+            params.files = true;
+            continue;
+        }
+        let usage = "usage: day07 [plan [--files] [--alternatives N]] [--disk N] [--needed N]";
+        let value = rest.next().unwrap_or_else(|| panic!("{}", usage));
+        let parsed = value.parse().ok().filter(|n: &i64| *n >= 0);
+        match (arg.as_str(), parsed) {
+            ("--disk", Some(disk)) => params.disk = disk,
+            ("--needed", Some(needed)) => params.needed = needed,
+            ("--alternatives", Some(n)) if planning => params.alternatives = n as usize,
+            _ => panic!("{}", usage),
+        }
+    }
 
-    let root_dir = parse_input(input);
+    if planning {
+        match plan::run(&root_dir, &params) {
+            Ok(out) => print!("{}", out),
+            Err(e) => {
+                eprintln!("{}", e);
+                process::exit(1);
+            }
+        }
+        return;
+    }
 
     println!("Part 1 - {}", part_1(&root_dir));
-    println!("Part 2 - {}", part_2(&root_dir));
+    match part_2(&root_dir, params.disk, params.needed) {
+        Ok(Some(0)) => println!("Part 2 - nothing has to be deleted"),
+        Ok(Some(size)) => println!("Part 2 - {}", size),
+        Ok(None) => println!("Part 2 - no directory frees enough space"),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(1);
+        }
+    }
 }
 
 fn part_1(root_dir: &Dir) -> i64 {
-    let mut sizes = HashMap::new();
-    root_dir.get_sizes(&mut sizes, vec!["".to_string()]);
-    sizes.values().filter(|s| **s <= 100000).sum()
+    root_dir
+        .dir_sizes("/")
+        .iter()
+        .map(|(_, size)| *size)
+        .filter(|size| *size <= 100000)
+        .sum()
 }
 
-fn part_2(root_dir: &Dir) -> i64 {
-    let mut sizes = HashMap::new();
-    root_dir.get_sizes(&mut sizes, vec!["".to_string()]);
-
-    let space_to_free = sizes.get("").unwrap() - 40000000;
-
-    *sizes
-        .values()
-        .filter(|s| **s >= space_to_free)
-        .min()
-        .unwrap()
+// part_2 is the size of the smallest directory that leaves `needed` free on the disk,
+// 0 when there is enough free space already
+fn part_2(root_dir: &Dir, disk: i64, needed: i64) -> Result<Option<i64>, String> {
+    let space_to_free = plan::to_free(root_dir, disk, needed)?;
+    if space_to_free <= 0 {
+        return Ok(Some(0));
+    }
+    Ok(plan::smallest_directory(root_dir, space_to_free).map(|(_, size)| size))
 }
 
-fn parse_input(input: String) -> Dir {
-    let lines: Vec<String> = input.split("\n").map(|s| s.to_string()).collect();
-
+// parse_input replays the transcript. A line starting with `$` is a command, `cd <path>` or `ls`,
+// and the lines after `ls` up to the next command are its output. Paths work like in a shell,
+// `/x/y` starts at the root and `a/../b` goes through a, and `cd` into a directory nobody
+// listed creates it. Blank lines are skipped. A bad line doesn't stop the replay, every
+// problem is reported with its line, one per line
+fn parse_input(input: &str) -> Result<Fs, String> {
+    let mut filesystem = Fs::new();
     let mut cwd: Vec<String> = Vec::new();
-    let mut root_dir = Dir::new();
-
-    let mut i = 0;
-    loop {
-        let cmd = match lines.get(i) {
-            Some(l) => l,
-            None => break,
-        };
-
-        if cmd.starts_with("$ cd /") {
-            cwd = Vec::new();
-            i += 1;
+    // the `ls` whose output is being read, with its line and what it listed so far
+    let mut listing: Option<(usize, Vec<(usize, Entry)>)> = None;
+    let mut errors: Vec<String> = Vec::new();
+    // the output of a bad command is skipped rather than reported line by line
+    let mut skipping = false;
+
+    for (i, line) in input.lines().enumerate() {
+        let line_nr = i + 1;
+        if line.trim().is_empty() {
             continue;
         }
 
-        if cmd.starts_with("$ cd ..") {
-            cwd.pop();
-            i += 1;
+        let Some(command) = line.strip_prefix('$') else {
+            match listing.as_mut() {
+                Some((_, entries)) => match parse_entry(line) {
+                    Ok(entry) => entries.push((line_nr, entry)),
+                    Err(e) => errors.push(format!("line {}: {}", line_nr, e)),
+                },
+                None if skipping => {}
+                None => errors.push(format!(
+                    "line {}: `{}` is neither a command nor the output of `ls`",
+                    line_nr, line
+                )),
+            }
             continue;
-        }
+        };
 
-        if cmd.starts_with("$ cd") {
-            let dir_name = cmd.chars().skip(5).collect();
-            cwd.push(dir_name);
-            i += 1;
-            continue;
+        // a command ends the output of the `ls` before it
+        if let Some((ls_line, entries)) = listing.take() {
+            if let Err(e) = filesystem.list(&cwd, ls_line, &entries) {
+                errors.push(format!("line {}: {}", ls_line, e));
+            }
         }
 
-        if cmd.starts_with("$ ls") {
-            while lines.get(i + 1).is_some() && !lines[i + 1].starts_with("$") {
-                let line = &lines[i + 1];
-                if line.starts_with("dir ") {
-                    let dir_name = line.chars().skip(4).collect();
-                    add_dir(&mut root_dir, &cwd, dir_name);
-                } else {
-                    let mut j = 0;
-                    loop {
-                        if !line.chars().nth(j).unwrap().is_numeric() {
-                            break;
-                        }
-
-                        j += 1;
-                    }
-
-                    let file_size: i64 = line.chars().take(j).collect::<String>().parse().unwrap();
-                    add_file(&mut root_dir, &cwd, file_size);
-                }
-
-                i += 1;
+        skipping = false;
+        let words: Vec<&str> = command.split_whitespace().collect();
+        let error = match words[..] {
+            ["cd", path] => {
+                // a `cd` that fails leaves the current directory where it was
+                let from = cwd.clone();
+                cd(&mut cwd, path);
+                filesystem
+                    .enter(&cwd, line_nr)
+                    .err()
+                    .inspect(|_| cwd = from)
             }
-
-            i += 1;
+            ["cd", ..] => Some("`cd` takes one directory".to_string()),
+            ["ls"] => {
+                listing = Some((line_nr, Vec::new()));
+                None
+            }
+            ["ls", ..] => Some("`ls` only lists the current directory".to_string()),
+            [name, ..] => Some(format!("unknown command `{}`", name)),
+            [] => Some("`$` without a command".to_string()),
+        };
+        if let Some(e) = error {
+            errors.push(format!("line {}: {}", line_nr, e));
+            skipping = true;
         }
     }
 
-    root_dir
-}
-
-fn add_dir(tree: &mut Dir, cwd: &Vec<String>, new_dir_name: String) {
-    let mut dir = tree;
-    for dir_name in cwd {
-        dir = dir
-            .directories
-            .get_mut(dir_name)
-            .expect("directory does not exist");
+    if let Some((ls_line, entries)) = listing {
+        if let Err(e) = filesystem.list(&cwd, ls_line, &entries) {
+            errors.push(format!("line {}: {}", ls_line, e));
+        }
     }
-    dir.directories.insert(new_dir_name, Dir::new());
-}
 
-fn add_file(tree: &mut Dir, cwd: &Vec<String>, file_size: i64) {
-    let mut dir = tree;
-    for dir_name in cwd {
-        dir = dir
-            .directories
-            .get_mut(dir_name)
-            .expect("directory does not exist");
+    match errors.is_empty() {
+        true => Ok(filesystem),
+        false => Err(errors.join("\n")),
     }
-    dir.files.push(file_size);
-}
-
-struct Dir {
-    files: Vec<i64>,
-    directories: HashMap<String, Dir>,
 }
 
-impl Dir {
-    fn new() -> Dir {
-        Dir {
-            directories: HashMap::new(),
-            files: Vec::new(),
+// cd follows a path from the current directory, `..` above the root stays at the root
+fn cd(cwd: &mut Vec<String>, path: &str) {
+    if path.starts_with('/') {
+        cwd.clear();
+    }
+    for name in path.split('/') {
+        match name {
+            "" | "." => {}
+            ".." => {
+                cwd.pop();
+            }
+            name => cwd.push(name.to_string()),
         }
     }
+}
 
-    fn get_sizes(&self, mut dst: &mut HashMap<String, i64>, path: Vec<String>) -> i64 {
-        let mut total = 0;
-        for (sub_name, sub_dir) in &self.directories {
-            let mut sub_path = path.clone();
-            sub_path.push(sub_name.clone());
-
-            let sub_size = sub_dir.get_sizes(&mut dst, sub_path);
-            total += sub_size;
+// parse_entry reads a line of a listing, `dir <name>` or `<size> <name>`
+fn parse_entry(line: &str) -> Result<Entry, String> {
+    match line.split_once(' ') {
+        Some(("dir", name)) if !name.is_empty() => Ok(Entry::Dir(name.to_string())),
+        Some((size, name)) if !name.is_empty() && size.chars().all(|c| c.is_ascii_digit()) => {
+            let size = size
+                .parse()
+                .map_err(|_| format!("invalid file size `{}`", size))?;
+            Ok(Entry::File(name.to_string(), size))
         }
-
-        let size = total + self.files.iter().sum::<i64>();
-        dst.insert(path.join("/"), size);
-        size
+        _ => Err(format!("invalid file `{}`", line)),
     }
 }
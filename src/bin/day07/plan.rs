@@ -0,0 +1,318 @@
+use std::{collections::HashSet, fmt::Write};
+
+use super::{vfs::join, Dir};
+
+// The cleanup planner looks for the smallest total of directories, and files if allowed,
+// that frees enough space. Deleting a directory deletes everything in it, so a plan never holds
+// something together with a directory it is in.
+//
+// The directories and files are laid out in the order a walk of the tree visits them, where
+// everything inside a directory comes right after it. Going through that list, an entry is
+// either skipped, which moves on to the next one and so into the directory, or deleted, which
+// jumps past everything inside it. The sums of the entries deleted so far, below the amount to
+// free, are kept as a bitset, so the work is the number of entries times the amount to free.
+
+// the most bytes the planner is willing to keep a bitset and a back pointer for
+const MAX_TO_FREE: usize = 50_000_000;
+
+pub struct Params {
+    pub disk: i64,
+    pub needed: i64,
+    pub files: bool,
+    pub alternatives: usize,
+}
+
+// Plan is a set of paths to delete, with their sizes
+pub struct Plan {
+    pub total: i64,
+    pub paths: Vec<(String, i64)>,
+}
+
+struct Entry {
+    path: String,
+    size: i64,
+    // the position right after everything inside the entry
+    end: usize,
+    file: bool,
+    // the files inside the entry, numbered in the order of the walk
+    files: (usize, usize),
+}
+
+pub fn run(root: &Dir, params: &Params) -> Result<String, String> {
+    let to_free = to_free(root, params.disk, params.needed)?;
+    let used = root.size();
+
+    let mut out = String::new();
+    writeln!(
+        out,
+        "Disk of {} with {} used and {} free, {} has to be free",
+        params.disk,
+        used,
+        params.disk - used,
+        params.needed
+    )
+    .unwrap();
+    if to_free <= 0 {
+        writeln!(out, "Nothing has to be deleted").unwrap();
+        return Ok(out);
+    }
+    writeln!(out, "{} has to be deleted", to_free).unwrap();
+
+    match smallest_directory(root, to_free) {
+        Some((path, size)) => writeln!(
+            out,
+            "Smallest directory - {} of {} ({} more than needed)",
+            path,
+            size,
+            size - to_free
+        )
+        .unwrap(),
+        None => writeln!(out, "Smallest directory - none is big enough").unwrap(),
+    }
+
+    let plans = plans(root, to_free, params.files, params.alternatives + 1)?;
+    if plans.is_empty() {
+        writeln!(out, "Nothing frees enough space").unwrap();
+    }
+    for (i, plan) in plans.iter().enumerate() {
+        let name = match i {
+            0 => "Best plan".to_string(),
+            _ => format!("Alternative {}", i),
+        };
+        writeln!(
+            out,
+            "{} - {} by deleting {} paths ({} more than needed)",
+            name,
+            plan.total,
+            plan.paths.len(),
+            plan.total - to_free
+        )
+        .unwrap();
+        for (path, size) in &plan.paths {
+            writeln!(out, "{:>12}  {}", size, path).unwrap();
+        }
+    }
+    Ok(out)
+}
+
+// to_free is how much has to be deleted to leave `needed` free, nothing when it is 0 or less.
+// A disk that can't hold the files, or that can never have `needed` free, is an error
+pub fn to_free(root: &Dir, disk: i64, needed: i64) -> Result<i64, String> {
+    let used = root.size();
+    if disk < used {
+        return Err(format!(
+            "the disk of {} is smaller than the {} the files take",
+            disk, used
+        ));
+    }
+    if needed > disk {
+        return Err(format!(
+            "{} can never be free on a disk of {}",
+            needed, disk
+        ));
+    }
+    Ok(needed - (disk - used))
+}
+
+// smallest_directory is the single directory part 2 is after
+pub fn smallest_directory(root: &Dir, to_free: i64) -> Option<(String, i64)> {
+    root.dir_sizes("/")
+        .into_iter()
+        .filter(|(_, size)| *size >= to_free)
+        .min_by_key(|(path, size)| (*size, path.clone()))
+}
+
+// plans finds the best plan and, as alternatives, the best plans that end with other entries,
+// `count` in all, smallest total first. Plans that delete the same files as a better one, like
+// a directory and the only file in it, are left out. Directories end in a slash
+pub fn plans(root: &Dir, to_free: i64, files: bool, count: usize) -> Result<Vec<Plan>, String> {
+    let to_free = usize::try_from(to_free).unwrap_or(0);
+    if to_free > MAX_TO_FREE {
+        return Err(format!(
+            "the planner can free up to {}, not {}",
+            MAX_TO_FREE, to_free
+        ));
+    }
+
+    let mut entries = Vec::new();
+    lay_out(root, "/", files, &mut entries, &mut 0);
+
+    // reachable holds the totals below to_free that can be deleted from the entries so far,
+    // first_reached the entry that made a total reachable first, which is enough to walk back
+    // to every entry of a plan as a total only ever becomes reachable once
+    let mut reachable = Bits::new(to_free);
+    reachable.set(0);
+    let mut first_reached = vec![usize::MAX; to_free];
+    // totals that include a directory land once the walk is past everything inside it
+    let mut pending: Vec<(usize, Bits)> = Vec::new();
+    // the best total that ends with every entry, with the total of the entries before it
+    let mut endings: Vec<(usize, usize, usize)> = Vec::new();
+
+    for i in 0..=entries.len() {
+        pending.retain(|(j, totals)| {
+            if entries[*j].end != i {
+                return true;
+            }
+            for (k, word) in totals.words.iter().enumerate() {
+                let mut new = word & !reachable.words[k];
+                reachable.words[k] |= new;
+                while new != 0 {
+                    first_reached[k * 64 + new.trailing_zeros() as usize] = *j;
+                    new &= new - 1;
+                }
+            }
+            false
+        });
+
+        let Some(entry) = entries.get(i) else {
+            break;
+        };
+        let size = entry.size as usize;
+        if size == 0 {
+            continue;
+        }
+        if size >= to_free {
+            endings.push((size, i, 0));
+            continue;
+        }
+        if let Some(before) = reachable.first_from(to_free - size) {
+            endings.push((before + size, i, before));
+        }
+        pending.push((i, reachable.shifted(size)));
+    }
+
+    endings.sort_unstable();
+    let mut plans = Vec::new();
+    let mut seen = HashSet::new();
+    for (total, last, mut before) in endings {
+        if plans.len() == count.max(1) {
+            break;
+        }
+
+        let mut chosen = vec![last];
+        while before != 0 {
+            let j = first_reached[before];
+            chosen.push(j);
+            before -= entries[j].size as usize;
+        }
+        chosen.sort_unstable();
+
+        // the files of a directory are numbered one after the other, so the deleted files are
+        // a few runs of numbers, which are the same for plans that delete the same files
+        let mut deleted: Vec<(usize, usize)> = Vec::new();
+        for j in &chosen {
+            let (start, end) = entries[*j].files;
+            match deleted.last_mut() {
+                Some(run) if run.1 == start => run.1 = end,
+                _ => deleted.push((start, end)),
+            }
+        }
+        if !seen.insert(deleted) {
+            continue;
+        }
+
+        plans.push(Plan {
+            total: total as i64,
+            paths: chosen
+                .into_iter()
+                .map(|j| {
+                    let entry = &entries[j];
+                    let path = match entry.file {
+                        true => entry.path.clone(),
+                        false => format!("{}/", entry.path.trim_end_matches('/')),
+                    };
+                    (path, entry.size)
+                })
+                .collect(),
+        });
+    }
+    Ok(plans)
+}
+
+// lay_out lists a directory and everything inside it in the order a walk visits them,
+// `next_file` is the number of the next file the walk comes across
+fn lay_out(dir: &Dir, path: &str, files: bool, entries: &mut Vec<Entry>, next_file: &mut usize) {
+    let at = entries.len();
+    entries.push(Entry {
+        path: path.to_string(),
+        size: dir.size(),
+        end: 0,
+        file: false,
+        files: (*next_file, 0),
+    });
+
+    for (name, sub_dir) in &dir.directories {
+        lay_out(sub_dir, &join(path, name), files, entries, next_file);
+    }
+    for (name, file) in &dir.files {
+        if files {
+            entries.push(Entry {
+                path: join(path, name),
+                size: file.size,
+                end: entries.len() + 1,
+                file: true,
+                files: (*next_file, *next_file + 1),
+            });
+        }
+        *next_file += 1;
+    }
+    entries[at].end = entries.len();
+    entries[at].files.1 = *next_file;
+}
+
+// Bits is a set of the numbers below len
+struct Bits {
+    words: Vec<u64>,
+    len: usize,
+}
+
+impl Bits {
+    fn new(len: usize) -> Bits {
+        Bits {
+            words: vec![0; len.div_ceil(64)],
+            len,
+        }
+    }
+
+    fn set(&mut self, i: usize) {
+        if i < self.len {
+            self.words[i / 64] |= 1 << (i % 64);
+        }
+    }
+
+    // shifted adds n to every number, dropping the ones that end up past len
+    fn shifted(&self, n: usize) -> Bits {
+        let mut shifted = Bits::new(self.len);
+        let (words, bits) = (n / 64, n % 64);
+        for k in (words..self.words.len()).rev() {
+            let mut word = self.words[k - words] << bits;
+            if bits > 0 && k > words {
+                word |= self.words[k - words - 1] >> (64 - bits);
+            }
+            shifted.words[k] = word;
+        }
+        // the last word can hold bits past len
+        if !self.len.is_multiple_of(64) {
+            if let Some(last) = shifted.words.last_mut() {
+                *last &= (1 << (self.len % 64)) - 1;
+            }
+        }
+        shifted
+    }
+
+    // first_from is the smallest number in the set that is at least from
+    fn first_from(&self, from: usize) -> Option<usize> {
+        if from >= self.len {
+            return None;
+        }
+        let mut k = from / 64;
+        let mut word = self.words[k] & (u64::MAX << (from % 64));
+        loop {
+            if word != 0 {
+                return Some(k * 64 + word.trailing_zeros() as usize);
+            }
+            k += 1;
+            word = *self.words.get(k)?;
+        }
+    }
+}
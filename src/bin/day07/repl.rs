@@ -0,0 +1,120 @@
+use advent2022::prompt::{self, Command};
+
+use super::{vfs::Node, Dir};
+
+pub fn run(root_dir: &Dir) {
+    prompt::run(
+        "day07",
+        root_dir,
+        &[
+            Command {
+                name: "size",
+                args: "<path>",
+                help: "total size of a directory, like /a/e",
+                run: size,
+                complete: Some(dir_paths),
+            },
+            Command {
+                name: "ls",
+                args: "[path]",
+                help: "directories and files in a directory",
+                run: ls,
+                complete: Some(dir_paths),
+            },
+            Command {
+                name: "stat",
+                args: "<path>",
+                help: "a file or a directory and the line it was listed on",
+                run: stat,
+                complete: Some(all_paths),
+            },
+            Command {
+                name: "smallest",
+                args: "<size>",
+                help: "the smallest directory of at least this size",
+                run: smallest,
+                complete: None,
+            },
+        ],
+    );
+}
+
+fn dir_paths(root_dir: &Dir) -> Vec<String> {
+    root_dir
+        .dir_sizes("/")
+        .into_iter()
+        .map(|(path, _)| path)
+        .collect()
+}
+
+fn all_paths(root_dir: &Dir) -> Vec<String> {
+    let mut paths = Vec::new();
+    for path in dir_paths(root_dir) {
+        if let Ok(dir) = root_dir.find(&path) {
+            paths.extend(dir.files.keys().map(|name| super::vfs::join(&path, name)));
+        }
+        paths.push(path);
+    }
+    paths
+}
+
+fn size(root_dir: &Dir, args: &[&str]) -> Result<String, String> {
+    let path = args.first().ok_or("missing <path>")?;
+    Ok(root_dir.find(path)?.size().to_string())
+}
+
+fn ls(root_dir: &Dir, args: &[&str]) -> Result<String, String> {
+    let dir = root_dir.find(args.first().unwrap_or(&"/"))?;
+
+    let mut lines: Vec<String> = dir
+        .directories
+        .iter()
+        .map(|(name, sub_dir)| format!("dir {} ({})", name, sub_dir.size()))
+        .collect();
+    lines.extend(
+        dir.files
+            .iter()
+            .map(|(name, file)| format!("{} {}", file.size, name)),
+    );
+
+    if lines.is_empty() {
+        return Ok("(empty)".to_string());
+    }
+    Ok(lines.join("\n"))
+}
+
+fn stat(root_dir: &Dir, args: &[&str]) -> Result<String, String> {
+    let path = args.first().ok_or("missing <path>")?;
+    Ok(match root_dir.lookup(path)? {
+        Node::File(file) => format!("file of {} bytes, listed on line {}", file.size, file.line),
+        Node::Dir(dir) => {
+            let listed = match dir.listed {
+                Some(line) => format!("its contents on line {}", line),
+                None => "its contents never".to_string(),
+            };
+            let seen = match dir.line {
+                0 => "the root".to_string(),
+                line => format!("listed on line {}", line),
+            };
+            format!(
+                "directory of {} bytes with {} directories and {} files, {}, {}",
+                dir.size(),
+                dir.directories.len(),
+                dir.files.len(),
+                seen,
+                listed
+            )
+        }
+    })
+}
+
+fn smallest(root_dir: &Dir, args: &[&str]) -> Result<String, String> {
+    let at_least: i64 = prompt::parse_arg(args, 0, "size")?;
+    root_dir
+        .dir_sizes("/")
+        .into_iter()
+        .filter(|(_, size)| *size >= at_least)
+        .min_by_key(|(path, size)| (*size, path.clone()))
+        .map(|(path, size)| format!("{} ({})", path, size))
+        .ok_or_else(|| format!("no directory is {} or bigger", at_least))
+}
@@ -0,0 +1,253 @@
+use std::collections::BTreeMap;
+
+// The filesystem as far as the transcript shows it. Every file and directory remembers the
+// line it was first listed on, and a directory that is listed more than once is merged with
+// what was listed before, so nothing is counted twice. Listings that disagree with an earlier
+// one are kept as conflicts, the first listing wins.
+
+#[derive(Debug, Clone)]
+pub struct File {
+    pub size: i64,
+    pub line: usize,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct Dir {
+    pub files: BTreeMap<String, File>,
+    pub directories: BTreeMap<String, Dir>,
+    // the line the directory was first listed on by its parent, 0 for the root
+    pub line: usize,
+    // the line of the first `ls` in the directory, None when it was never listed
+    pub listed: Option<usize>,
+}
+
+// Entry is one line of an `ls` listing
+pub enum Entry {
+    Dir(String),
+    File(String, i64),
+}
+
+impl Entry {
+    fn name(&self) -> &str {
+        match self {
+            Entry::Dir(name) | Entry::File(name, _) => name,
+        }
+    }
+}
+
+pub enum Node<'a> {
+    Dir(&'a Dir),
+    File(&'a File),
+}
+
+#[derive(Debug, Default)]
+pub struct Fs {
+    pub root: Dir,
+    pub conflicts: Vec<String>,
+}
+
+impl Fs {
+    pub fn new() -> Fs {
+        Fs::default()
+    }
+
+    // enter makes sure the directory at path exists, creating what is missing on the way.
+    // A directory that shows up that way in a directory that was listed is a conflict,
+    // going into a file is an error
+    pub fn enter(&mut self, path: &[String], line: usize) -> Result<(), String> {
+        let mut dir = &mut self.root;
+        for (depth, name) in path.iter().enumerate() {
+            if let Some(file) = dir.files.get(name) {
+                return Err(format!(
+                    "`{}` in {} is a file on line {}",
+                    name,
+                    show_path(&path[..depth]),
+                    file.line
+                ));
+            }
+            if let (false, Some(listed)) = (dir.directories.contains_key(name), dir.listed) {
+                self.conflicts.push(format!(
+                    "line {}: `{}` isn't in the listing of {} on line {}",
+                    line,
+                    name,
+                    show_path(&path[..depth]),
+                    listed
+                ));
+            }
+            dir = dir.directories.entry(name.clone()).or_insert_with(|| Dir {
+                line,
+                ..Dir::default()
+            });
+        }
+        Ok(())
+    }
+
+    // list merges the listing of the directory at path into what is known, `line` is the line
+    // of the `ls` and every entry comes with its own line
+    pub fn list(
+        &mut self,
+        path: &[String],
+        line: usize,
+        entries: &[(usize, Entry)],
+    ) -> Result<(), String> {
+        let dir = self.root.find_mut(path)?;
+        let shown = show_path(path);
+        let mut conflicts = Vec::new();
+
+        if let Some(first) = dir.listed {
+            // a second listing has to show the same entries as the first
+            for (name, line_seen) in dir
+                .files
+                .iter()
+                .map(|(name, file)| (name, file.line))
+                .chain(dir.directories.iter().map(|(name, dir)| (name, dir.line)))
+            {
+                if !entries.iter().any(|(_, entry)| entry.name() == name) {
+                    conflicts.push(format!(
+                        "line {}: the listing of {} leaves out `{}`, which line {} shows",
+                        line, shown, name, line_seen
+                    ));
+                }
+            }
+            for (entry_line, entry) in entries {
+                let name = entry.name();
+                if !dir.files.contains_key(name) && !dir.directories.contains_key(name) {
+                    conflicts.push(format!(
+                        "line {}: `{}` isn't in the listing of {} on line {}",
+                        entry_line, name, shown, first
+                    ));
+                }
+            }
+        } else {
+            dir.listed = Some(line);
+        }
+
+        for (entry_line, entry) in entries {
+            let entry_line = *entry_line;
+            match entry {
+                Entry::Dir(name) => {
+                    if let Some(file) = dir.files.get(name) {
+                        conflicts.push(format!(
+                            "line {}: `{}` in {} is a file on line {}",
+                            entry_line, name, shown, file.line
+                        ));
+                        continue;
+                    }
+                    // listing a directory again keeps everything already known about it
+                    dir.directories.entry(name.clone()).or_insert_with(|| Dir {
+                        line: entry_line,
+                        ..Dir::default()
+                    });
+                }
+                Entry::File(name, size) => {
+                    if let Some(sub_dir) = dir.directories.get(name) {
+                        conflicts.push(format!(
+                            "line {}: `{}` in {} is a directory on line {}",
+                            entry_line, name, shown, sub_dir.line
+                        ));
+                        continue;
+                    }
+                    match dir.files.get(name) {
+                        Some(file) if file.size != *size => conflicts.push(format!(
+                            "line {}: `{}` in {} has size {}, but {} on line {}",
+                            entry_line, name, shown, size, file.size, file.line
+                        )),
+                        Some(_) => {}
+                        None => {
+                            dir.files.insert(
+                                name.clone(),
+                                File {
+                                    size: *size,
+                                    line: entry_line,
+                                },
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        self.conflicts.extend(conflicts);
+        Ok(())
+    }
+}
+
+impl Dir {
+    // size is the total size of the files in the directory and everything below it
+    pub fn size(&self) -> i64 {
+        self.files.values().map(|file| file.size).sum::<i64>()
+            + self.directories.values().map(Dir::size).sum::<i64>()
+    }
+
+    // dir_sizes lists this directory and every directory below it with its total size,
+    // `path` is the path of this directory, like /a/e
+    pub fn dir_sizes(&self, path: &str) -> Vec<(String, i64)> {
+        let mut sizes = Vec::new();
+        self.collect_sizes(path, &mut sizes);
+        sizes
+    }
+
+    fn collect_sizes(&self, path: &str, sizes: &mut Vec<(String, i64)>) -> i64 {
+        let at = sizes.len();
+        sizes.push((path.to_string(), 0));
+
+        let mut total = self.files.values().map(|file| file.size).sum::<i64>();
+        for (name, dir) in &self.directories {
+            total += dir.collect_sizes(&join(path, name), sizes);
+        }
+        sizes[at].1 = total;
+        total
+    }
+
+    // find looks up a directory by its path, which may leave out the leading / and trailing slashes
+    pub fn find(&self, path: &str) -> Result<&Dir, String> {
+        let mut dir = self;
+        for name in path.split('/').filter(|n| !n.is_empty()) {
+            dir = dir
+                .directories
+                .get(name)
+                .ok_or_else(|| format!("no directory `{}` in {}", name, path))?;
+        }
+        Ok(dir)
+    }
+
+    // lookup finds a file or a directory by its path
+    pub fn lookup(&self, path: &str) -> Result<Node<'_>, String> {
+        let path = path.trim_end_matches('/');
+        let (parent, name) = path.rsplit_once('/').unwrap_or(("", path));
+        let dir = self.find(parent)?;
+        if name.is_empty() {
+            return Ok(Node::Dir(dir));
+        }
+        match (dir.directories.get(name), dir.files.get(name)) {
+            (Some(dir), _) => Ok(Node::Dir(dir)),
+            (None, Some(file)) => Ok(Node::File(file)),
+            (None, None) => Err(format!(
+                "nothing called `{}` in {}",
+                name,
+                if parent.is_empty() { "/" } else { parent }
+            )),
+        }
+    }
+
+    fn find_mut(&mut self, path: &[String]) -> Result<&mut Dir, String> {
+        let mut dir = self;
+        for name in path {
+            dir = dir
+                .directories
+                .get_mut(name)
+                .ok_or_else(|| format!("directory `{}` does not exist", show_path(path)))?;
+        }
+        Ok(dir)
+    }
+}
+
+// join adds a name to a path, where the root is /
+pub fn join(path: &str, name: &str) -> String {
+    format!("{}/{}", path.trim_end_matches('/'), name)
+}
+
+// show_path writes the names from the root down as a path like /a/e
+pub fn show_path(path: &[String]) -> String {
+    format!("/{}", path.join("/"))
+}
@@ -0,0 +1,216 @@
+use std::fmt::Write;
+
+use super::Dir;
+
+// Views of the filesystem the way the usual tools print one: `tree -s` draws every directory
+// and file with its size, `du | sort -h` lists the directories from small to big, and json
+// writes the whole tree out. Sizes are bytes, or 1024 based units with `human`, and a depth
+// limit stops at that many levels below the root.
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Options {
+    pub human: bool,
+    pub depth: Option<usize>,
+}
+
+impl Options {
+    // parse reads `-h` and `--depth N` from the arguments after the view
+    pub fn parse(args: &[String]) -> Result<Options, String> {
+        let mut options = Options::default();
+        let mut rest = args.iter();
+        while let Some(arg) = rest.next() {
+            match arg.as_str() {
+                "-h" => options.human = true,
+                "--depth" => {
+                    let depth = rest.next().ok_or("missing value for --depth")?;
+                    options.depth = Some(
+                        depth
+                            .parse()
+                            .map_err(|_| format!("invalid value `{}` for --depth", depth))?,
+                    );
+                }
+                _ => return Err(format!("unexpected argument `{}`", arg)),
+            }
+        }
+        Ok(options)
+    }
+
+    fn size(&self, size: i64) -> String {
+        match self.human {
+            true => human(size),
+            false => size.to_string(),
+        }
+    }
+
+    fn within(&self, depth: usize) -> bool {
+        self.depth.is_none_or(|limit| depth <= limit)
+    }
+}
+
+// human writes a size like `du -h`, rounding up, with one decimal below 10 of a unit
+pub fn human(size: i64) -> String {
+    if size < 1024 {
+        return size.to_string();
+    }
+
+    let mut value = size as f64;
+    let mut units = ["K", "M", "G", "T", "P", "E"].iter();
+    let mut unit = "";
+    while value >= 1024.0 {
+        value /= 1024.0;
+        unit = units.next().unwrap_or(&"E");
+    }
+
+    let tenths = (value * 10.0).ceil() / 10.0;
+    if tenths < 10.0 {
+        format!("{:.1}{}", tenths, unit)
+    } else {
+        format!("{}{}", value.ceil(), unit)
+    }
+}
+
+// tree draws the directories and files below the root like `tree -s`, sorted by name,
+// and counts what it drew at the end
+pub fn tree(root: &Dir, options: Options) -> String {
+    let mut out = format!("[{:>10}]  /\n", options.size(root.size()));
+    let (mut dirs, mut files) = (0, 0);
+    draw(root, "", 1, options, &mut out, &mut dirs, &mut files);
+    writeln!(out, "\n{} directories, {} files", dirs, files).unwrap();
+    out
+}
+
+fn draw(
+    dir: &Dir,
+    prefix: &str,
+    depth: usize,
+    options: Options,
+    out: &mut String,
+    dirs: &mut usize,
+    files: &mut usize,
+) {
+    if !options.within(depth) {
+        return;
+    }
+
+    // tree mixes directories and files in one list sorted by name
+    let mut entries: Vec<(&String, Option<&Dir>, i64)> = dir
+        .directories
+        .iter()
+        .map(|(name, sub_dir)| (name, Some(sub_dir), sub_dir.size()))
+        .chain(dir.files.iter().map(|(name, file)| (name, None, file.size)))
+        .collect();
+    entries.sort_by_key(|(name, _, _)| *name);
+
+    for (i, (name, sub_dir, size)) in entries.iter().enumerate() {
+        let last = i + 1 == entries.len();
+        let branch = if last { "└── " } else { "├── " };
+        writeln!(
+            out,
+            "{}{}[{:>10}]  {}",
+            prefix,
+            branch,
+            options.size(*size),
+            name
+        )
+        .unwrap();
+
+        match sub_dir {
+            Some(sub_dir) => {
+                *dirs += 1;
+                let prefix = format!("{}{}", prefix, if last { "    " } else { "│   " });
+                draw(sub_dir, &prefix, depth + 1, options, out, dirs, files);
+            }
+            None => *files += 1,
+        }
+    }
+}
+
+// du lists every directory with its total size, smallest first like `du | sort -h`,
+// ties are kept in path order
+pub fn du(root: &Dir, options: Options) -> String {
+    let mut sizes: Vec<(String, i64)> = root
+        .dir_sizes("/")
+        .into_iter()
+        .filter(|(path, _)| options.within(path.matches('/').count() - usize::from(path == "/")))
+        .collect();
+    sizes.sort_by(|(a_path, a_size), (b_path, b_size)| a_size.cmp(b_size).then(a_path.cmp(b_path)));
+
+    let mut out = String::new();
+    for (path, size) in sizes {
+        writeln!(out, "{}\t{}", options.size(size), path).unwrap();
+    }
+    out
+}
+
+// json writes the tree as nested objects with the line every entry was listed on,
+// sizes are always in bytes
+pub fn json(root: &Dir, options: Options) -> String {
+    let mut out = String::new();
+    json_dir(root, "/", 0, options, &mut out);
+    out.push('\n');
+    out
+}
+
+fn json_dir(dir: &Dir, name: &str, depth: usize, options: Options, out: &mut String) {
+    let indent = "  ".repeat(depth);
+    write!(
+        out,
+        "{}{{\"name\": {}, \"type\": \"dir\", \"size\": {}, \"line\": {}",
+        indent,
+        json_string(name),
+        dir.size(),
+        dir.line
+    )
+    .unwrap();
+
+    // below the depth limit directories come without their contents
+    if !options.within(depth + 1) {
+        out.push('}');
+        return;
+    }
+    if dir.directories.is_empty() && dir.files.is_empty() {
+        out.push_str(", \"contents\": []}");
+        return;
+    }
+
+    out.push_str(", \"contents\": [\n");
+    let mut first = true;
+    for (sub_name, sub_dir) in &dir.directories {
+        if !first {
+            out.push_str(",\n");
+        }
+        first = false;
+        json_dir(sub_dir, sub_name, depth + 1, options, out);
+    }
+    for (file_name, file) in &dir.files {
+        if !first {
+            out.push_str(",\n");
+        }
+        first = false;
+        write!(
+            out,
+            "{}  {{\"name\": {}, \"type\": \"file\", \"size\": {}, \"line\": {}}}",
+            indent,
+            json_string(file_name),
+            file.size,
+            file.line
+        )
+        .unwrap();
+    }
+    write!(out, "\n{}]}}", indent).unwrap();
+}
+
+// json_string quotes a name, escaping what JSON needs escaped
+fn json_string(s: &str) -> String {
+    let mut out = String::from("\"");
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            ch if (ch as u32) < 0x20 => write!(out, "\\u{:04x}", ch as u32).unwrap(),
+            ch => out.push(ch),
+        }
+    }
+    out.push('"');
+    out
+}
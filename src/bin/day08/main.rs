@@ -1,42 +1,62 @@
 use std::{collections::HashSet, fs};
 
+use advent2022::{fuzz, input_path};
+
 fn main() {
+    fuzz::exit_if_requested("day08", parse_grid);
+
     // let input = fs::read_to_string("input/day08-test.txt")
-    let input = fs::read_to_string("input/day08.txt")
-        .expect("unable to read file")
-        .split("\n")
-        .map(|line| line.chars().collect::<Vec<char>>())
-        .collect::<Vec<Vec<char>>>();
+    let input = fs::read_to_string(input_path("day08")).expect("unable to read file");
+    let input = parse_grid(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
 
     println!("Part 1 - {}", part_1(&input));
     println!("Part 2 - {}", part_2(&input));
 }
 
-fn part_1(input: &Vec<Vec<char>>) -> i64 {
+// parse_grid checks that the trees form a rectangle of digits
+fn parse_grid(input: &str) -> Result<Vec<Vec<char>>, String> {
+    let grid: Vec<Vec<char>> = input
+        .split("\n")
+        .map(|line| line.chars().collect())
+        .collect();
+
+    for (i, row) in grid.iter().enumerate() {
+        if row.is_empty() || row.len() != grid[0].len() {
+            return Err(format!("line {}: expected {} trees", i + 1, grid[0].len()));
+        }
+        if let Some(ch) = row.iter().find(|ch| !ch.is_ascii_digit()) {
+            return Err(format!("line {}: invalid tree height `{}`", i + 1, ch));
+        }
+    }
+
+    Ok(grid)
+}
+
+fn part_1(input: &[Vec<char>]) -> i64 {
     let mut visible_tree_indices = HashSet::new();
     let mut previous;
 
-    for row in 0..input.len() {
+    for (row, trees) in input.iter().enumerate() {
         // from left
         previous = '/';
-        for col in 0..input[row].len() {
-            if input[row][col] > previous {
+        for (col, tree) in trees.iter().enumerate() {
+            if *tree > previous {
                 visible_tree_indices.insert((row, col));
-                previous = input[row][col]
+                previous = *tree
             }
-            if input[row][col] == '9' {
+            if *tree == '9' {
                 break;
             }
         }
 
         // from right
         previous = '/';
-        for col in (0..input[row].len()).rev() {
-            if input[row][col] > previous {
+        for (col, tree) in trees.iter().enumerate().rev() {
+            if *tree > previous {
                 visible_tree_indices.insert((row, col));
-                previous = input[row][col]
+                previous = *tree
             }
-            if input[row][col] == '9' {
+            if *tree == '9' {
                 break;
             }
         }
@@ -45,12 +65,12 @@ fn part_1(input: &Vec<Vec<char>>) -> i64 {
     for col in 0..input[0].len() {
         // from top
         previous = '/';
-        for row in 0..input.len() {
-            if input[row][col] > previous {
+        for (row, trees) in input.iter().enumerate() {
+            if trees[col] > previous {
                 visible_tree_indices.insert((row, col));
-                previous = input[row][col]
+                previous = trees[col]
             }
-            if input[row][col] == '9' {
+            if trees[col] == '9' {
                 break;
             }
         }
@@ -71,21 +91,19 @@ fn part_1(input: &Vec<Vec<char>>) -> i64 {
     visible_tree_indices.len() as i64
 }
 
-
-
-fn part_2(input: &Vec<Vec<char>>) -> i64 {
+fn part_2(input: &[Vec<char>]) -> i64 {
     let mut scenic_scores = Vec::new();
 
-    for row in 0..input.len() {
-        for col in 0..input.len() {
-            scenic_scores.push(scenic_score(&input, (row, col)));
+    for (row, trees) in input.iter().enumerate() {
+        for col in 0..trees.len() {
+            scenic_scores.push(scenic_score(input, (row, col)));
         }
     }
 
     scenic_scores.into_iter().max().unwrap()
 }
 
-fn scenic_score(input: &Vec<Vec<char>>, location: (usize, usize)) -> i64 {
+fn scenic_score(input: &[Vec<char>], location: (usize, usize)) -> i64 {
     // outside trees are zero
     if location.0 == 0
         || location.0 == input.len() - 1
@@ -1,14 +1,18 @@
 use std::{collections::HashSet, fs, time::Instant};
 
+use advent2022::{fuzz, input_path};
+
 fn main() {
-    let input_str = fs::read_to_string("input/day09.txt").expect("failed to read file");
+    fuzz::exit_if_requested("day09", parse_input);
+
+    let input_str = fs::read_to_string(input_path("day09")).expect("failed to read file");
 
     let time = Instant::now();
 
     // parse into a direction and number of fields moved
-    let input = parse_input(&input_str);
+    let input = parse_input(&input_str).unwrap_or_else(|e| panic!("invalid input: {}", e));
 
-    let mut knots = vec![(0, 0); 10];
+    let mut knots = [(0, 0); 10];
 
     // track all positions of the second and last knot
     let mut visited_1 = HashSet::with_capacity(10000);
@@ -42,20 +46,33 @@ fn main() {
     println!("Total execution time: {:?}", time.elapsed());
 }
 
-fn parse_input<'a>(input: &'a String) -> impl Iterator<Item = ((i64, i64), i64)> + 'a {
-    input.split("\n").map(|line| {
-        let mut ch = line.chars();
-        let direction: (i64, i64) = match ch.next().unwrap() {
-            'U' => (0, 1),
-            'D' => (0, -1),
-            'L' => (-1, 0),
-            'R' => (1, 0),
-            _ => panic!("unexpected direction character"),
-        };
-        let distance = ch.skip(1).collect::<String>().parse::<i64>().unwrap();
-
-        (direction, distance)
-    })
+// a direction and the number of fields moved
+type Step = ((i64, i64), i64);
+
+fn parse_input(input: &str) -> Result<Vec<Step>, String> {
+    input
+        .split("\n")
+        .enumerate()
+        .map(|(i, line)| {
+            let mut ch = line.chars();
+            let direction: (i64, i64) = match ch.next() {
+                Some('U') => (0, 1),
+                Some('D') => (0, -1),
+                Some('L') => (-1, 0),
+                Some('R') => (1, 0),
+                _ => return Err(format!("line {}: unexpected direction", i + 1)),
+            };
+            if ch.next() != Some(' ') {
+                return Err(format!("line {}: expected a space", i + 1));
+            }
+            let distance = ch
+                .collect::<String>()
+                .parse::<u32>()
+                .map_err(|_| format!("line {}: invalid distance", i + 1))?;
+
+            Ok((direction, distance as i64))
+        })
+        .collect()
 }
 
 fn move_tail(h: &(i64, i64), t: &mut (i64, i64)) {
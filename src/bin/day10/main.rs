@@ -1,38 +1,44 @@
 use std::{fs, time::Instant};
 
+use advent2022::{fuzz, input_path};
+
+enum Instruction {
+    Noop,
+    Addx(i64),
+}
+
 fn main() {
+    fuzz::exit_if_requested("day10", parse_program);
+
     // let input = fs::read_to_string("input/day10-test.txt").unwrap();
-    let input = fs::read_to_string("input/day10.txt").unwrap();
+    let input = fs::read_to_string(input_path("day10")).unwrap();
     let time = Instant::now();
 
+    let program = parse_program(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
+
     let mut register_values = Vec::new();
     register_values.push(0); // we start at 1 so write any value at index 0
     let mut register = 1;
 
-    for line in input.split("\n") {
-        let cmd = line.chars().take(4).collect::<String>();
-
+    for instruction in program {
         // one cycle
         register_values.push(register);
 
-        match cmd.as_str() {
-            "noop" => {}
-            "addx" => {
+        match instruction {
+            Instruction::Noop => {}
+            Instruction::Addx(v) => {
                 // an extra cycle
                 register_values.push(register);
 
                 // add value to register
-                register += line
-                    .chars()
-                    .skip(5)
-                    .collect::<String>()
-                    .parse::<i64>()
-                    .unwrap();
+                register += v;
             }
-            _ => panic!("unexpected input"),
         }
     }
 
+    // once the program ends the register keeps its value for the rest of the screen
+    register_values.resize(register_values.len().max(241), register);
+
     let answer_1: i64 = [20, 60, 100, 140, 180, 220]
         .map(|cycle| register_values[cycle as usize] * cycle)
         .iter()
@@ -56,3 +62,21 @@ fn main() {
 
     println!("Total execution time: {:?}", time.elapsed());
 }
+
+fn parse_program(input: &str) -> Result<Vec<Instruction>, String> {
+    input
+        .split("\n")
+        .enumerate()
+        .map(|(i, line)| {
+            if line == "noop" {
+                return Ok(Instruction::Noop);
+            }
+
+            // i32 values can never add up to more than fits in the i64 register
+            line.strip_prefix("addx ")
+                .and_then(|v| v.parse::<i32>().ok())
+                .map(|v| Instruction::Addx(v as i64))
+                .ok_or_else(|| format!("line {}: unexpected input `{}`", i + 1, line))
+        })
+        .collect()
+}
@@ -1,4 +1,8 @@
-use std::{fs, time::Instant};
+use std::{fs, process, time::Instant};
+
+use advent2022::{fuzz, input_path, prompt};
+
+mod repl;
 
 #[derive(Debug, Clone)]
 struct Monkey {
@@ -10,127 +14,196 @@ struct Monkey {
 }
 
 fn main() {
-    println!("Day 11");
+    fuzz::exit_if_requested("day11", parse_monkeys);
 
-    let input = fs::read_to_string("input/day11.txt").unwrap();
+    let input = fs::read_to_string(input_path("day11")).unwrap();
     let t = Instant::now();
 
-    let monkeys = parse_monkeys(input);
+    let monkeys = parse_monkeys(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
+
+    if prompt::requested() {
+        repl::run(&monkeys);
+        return;
+    }
 
-    println!("Part 1 - {}", part_1(monkeys.clone()));
-    println!("Part 2 - {}", part_2(monkeys.clone()));
+    println!("Day 11");
+    let answer = |business: Result<i64, String>| {
+        business.unwrap_or_else(|e| {
+            eprintln!("{}", e);
+            process::exit(1);
+        })
+    };
+    println!("Part 1 - {}", answer(part_1(monkeys.clone())));
+    println!("Part 2 - {}", answer(part_2(monkeys.clone())));
 
     println!("Total execution time: {:?}", t.elapsed());
 }
 
-fn parse_monkeys(input: String) -> [Monkey; 8] {
-    input
+fn parse_monkeys(input: &str) -> Result<Vec<Monkey>, String> {
+    let monkeys = input
         .split("\n\n")
-        .map(|block| parse_monkey(block))
-        .collect::<Vec<Monkey>>()
-        .try_into()
-        .unwrap()
+        .enumerate()
+        .map(|(i, block)| parse_monkey(block).map_err(|e| format!("monkey {}: {}", i, e)))
+        .collect::<Result<Vec<Monkey>, String>>()?;
+
+    // monkey business takes the two busiest monkeys, and items can only go to monkeys that exist
+    if monkeys.len() < 2 {
+        return Err(format!(
+            "expected at least 2 monkeys, got {}",
+            monkeys.len()
+        ));
+    }
+    for (i, monkey) in monkeys.iter().enumerate() {
+        for target in [monkey.true_monkey, monkey.false_monkey] {
+            if target >= monkeys.len() || target == i {
+                return Err(format!(
+                    "monkey {}: can't throw to monkey {}, there are monkeys 0 to {}",
+                    i,
+                    target,
+                    monkeys.len() - 1
+                ));
+            }
+        }
+    }
+    divisor_lcm(&monkeys)?;
+    Ok(monkeys)
 }
 
-fn parse_monkey(input: &str) -> Monkey {
+fn parse_monkey(input: &str) -> Result<Monkey, String> {
     let lines: Vec<&str> = input.split("\n").collect();
-    Monkey {
-        items: lines[1]
-            .strip_prefix("  Starting items: ")
-            .unwrap()
+    if lines.len() != 6 {
+        return Err(format!("expected 6 lines, got {}", lines.len()));
+    }
+    let field = |line: usize, prefix: &str| {
+        lines[line]
+            .strip_prefix(prefix)
+            .ok_or_else(|| format!("expected `{}`", prefix.trim()))
+    };
+    let number = |line: usize, prefix: &str| {
+        field(line, prefix)?
+            .parse::<usize>()
+            .map_err(|_| format!("invalid number after `{}`", prefix.trim()))
+    };
+
+    let monkey = Monkey {
+        items: field(1, "  Starting items: ")?
             .split(", ")
-            .map(|v| v.parse::<i64>().unwrap())
-            .collect(),
-        operation: lines[2]
-            .strip_prefix("  Operation: new = ")
-            .unwrap()
+            .map(|v| {
+                v.parse::<i64>()
+                    .map_err(|_| format!("invalid item `{}`", v))
+            })
+            .collect::<Result<_, _>>()?,
+        operation: field(2, "  Operation: new = ")?
             .split(" ")
             .map(|s| s.to_string())
             .collect::<Vec<String>>()
             .try_into()
-            .unwrap(),
-        test_divisor: lines[3]
-            .strip_prefix("  Test: divisible by ")
-            .unwrap()
-            .parse()
-            .unwrap(),
-        true_monkey: lines[4]
-            .strip_prefix("    If true: throw to monkey ")
-            .unwrap()
-            .parse()
-            .unwrap(),
-        false_monkey: lines[5]
-            .strip_prefix("    If false: throw to monkey ")
-            .unwrap()
-            .parse()
-            .unwrap(),
+            .map_err(|_| "operation needs two operands and an operator".to_string())?,
+        test_divisor: number(3, "  Test: divisible by ")? as i64,
+        true_monkey: number(4, "    If true: throw to monkey ")?,
+        false_monkey: number(5, "    If false: throw to monkey ")?,
+    };
+
+    // the game can only handle these two operators, and small numbers keep the worry levels in check
+    if !["+", "*"].contains(&monkey.operation[1].as_str()) {
+        return Err(format!("unexpected operator `{}`", monkey.operation[1]));
     }
+    for operand in [&monkey.operation[0], &monkey.operation[2]] {
+        if operand != "old" && operand.parse::<u32>().is_err() {
+            return Err(format!("invalid operand `{}`", operand));
+        }
+    }
+    if monkey.test_divisor == 0 || monkey.test_divisor > u32::MAX as i64 {
+        return Err(format!("invalid divisor {}", monkey.test_divisor));
+    }
+    Ok(monkey)
 }
 
-fn part_1(mut monkeys: [Monkey; 8]) -> i64 {
-    let mut monkey_business = [0; 8];
-
-    for _ in 0..20 {
-        for i in 0..8 {
-            monkey_business[i] += monkeys[i].items.len() as i64;
+fn part_1(mut monkeys: Vec<Monkey>) -> Result<i64, String> {
+    Ok(business(&play(&mut monkeys, 20, true)?))
+}
 
-            for mut worry in monkeys[i].items.clone() {
-                // INSPECTION
-                worry = inspect(&monkeys[i].operation, worry);
+fn part_2(mut monkeys: Vec<Monkey>) -> Result<i64, String> {
+    Ok(business(&play(&mut monkeys, 10000, false)?))
+}
 
-                // RELIEF
-                worry = worry / 3;
+// business multiplies the inspections of the two busiest monkeys
+fn business(inspections: &[i64]) -> i64 {
+    let mut inspections = inspections.to_vec();
+    inspections.sort_unstable_by(|a, b| b.cmp(a));
+    inspections[0] * inspections[1]
+}
 
-                // TEST & THROW
-                test_and_throw(&mut monkeys, i, worry);
-            }
+// divisor_lcm is the least common multiple of the divisors. Worry levels without relief are
+// kept below it, so it has to be small enough that squaring one of them still fits
+fn divisor_lcm(monkeys: &[Monkey]) -> Result<i64, String> {
+    let too_big = || {
+        "the divisors have a least common multiple too big to keep the worry levels in check"
+            .to_string()
+    };
+    let lcm = monkeys.iter().try_fold(1i64, |lcm, monkey| {
+        (lcm / gcd(lcm, monkey.test_divisor))
+            .checked_mul(monkey.test_divisor)
+            .ok_or_else(too_big)
+    })?;
+    lcm.checked_mul(lcm).ok_or_else(too_big)?;
+    Ok(lcm)
+}
 
-            monkeys[i].items.clear();
-        }
+fn gcd(a: i64, b: i64) -> i64 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
     }
-
-    monkey_business.sort_unstable();
-    monkey_business[6] * monkey_business[7]
 }
 
-fn part_2(mut monkeys: [Monkey; 8]) -> i64 {
-    let mut monkey_business = [0; 8];
-    let divisor_product = monkeys
-        .iter()
-        .fold(1, |acc, monkey| acc * monkey.test_divisor);
+// play runs the rounds and returns how many items each monkey inspected.
+// Without relief the worry levels are kept small by taking the remainder
+// of the least common multiple of all divisors, which doesn't change the outcome of any test.
+// With relief nothing keeps them small, so a worry level that overflows is an error
+fn play(monkeys: &mut [Monkey], rounds: usize, relief: bool) -> Result<Vec<i64>, String> {
+    let mut monkey_business = vec![0; monkeys.len()];
+    let divisor_lcm = divisor_lcm(monkeys)?;
 
-    for _ in 0..10000 {
-        for i in 0..8 {
+    for round in 1..=rounds {
+        for i in 0..monkeys.len() {
             monkey_business[i] += monkeys[i].items.len() as i64;
 
             for mut worry in monkeys[i].items.clone() {
                 // INSPECTION
-                worry = inspect(&monkeys[i].operation, worry);
-
-                // NO RELIEF, PREVENT OVERFLOW
-                worry = worry % divisor_product;
+                worry = inspect(&monkeys[i].operation, worry).ok_or_else(|| {
+                    format!("a worry level of monkey {} overflows in round {}", i, round)
+                })?;
+
+                if relief {
+                    // RELIEF
+                    worry /= 3;
+                } else {
+                    // NO RELIEF, PREVENT OVERFLOW
+                    worry %= divisor_lcm;
+                }
 
                 // TEST & THROW
-                test_and_throw(&mut monkeys, i, worry);
+                test_and_throw(monkeys, i, worry);
             }
 
             monkeys[i].items.clear();
         }
     }
 
-    monkey_business.sort_unstable();
-    monkey_business[6] * monkey_business[7]
+    Ok(monkey_business)
 }
 
 // inspect returns the new worry level after inspection,
-// not accounting for any post-inspection relief
-fn inspect(operation: &[String; 3], worry: i64) -> i64 {
+// not accounting for any post-inspection relief, or None when it doesn't fit
+fn inspect(operation: &[String; 3], worry: i64) -> Option<i64> {
     let v1 = parse_operand(operation[0].as_str(), worry);
     let v2 = parse_operand(operation[2].as_str(), worry);
 
     match operation[1].as_str() {
-        "*" => v1 * v2,
-        "+" => v1 + v2,
+        "*" => v1.checked_mul(v2),
+        "+" => v1.checked_add(v2),
         x => panic!("unexpected operator: {}", x),
     }
 }
@@ -144,7 +217,7 @@ fn parse_operand(operand: &str, worry: i64) -> i64 {
 
 // test_and_throw throws an item from monkey i to the correct monkey
 // it does not remove the item from monkey i's item list
-fn test_and_throw(monkeys: &mut [Monkey; 8], i: usize, worry: i64) {
+fn test_and_throw(monkeys: &mut [Monkey], i: usize, worry: i64) {
     if worry % monkeys[i].test_divisor == 0 {
         monkeys[monkeys[i].true_monkey].items.push(worry);
     } else {
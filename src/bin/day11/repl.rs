@@ -0,0 +1,86 @@
+use advent2022::prompt::{self, Command};
+
+use super::{business as monkey_business, play, Monkey};
+
+pub fn run(monkeys: &[Monkey]) {
+    prompt::run(
+        "day11",
+        monkeys,
+        &[
+            Command {
+                name: "state",
+                args: "<rounds> [worried]",
+                help: "items and inspections of every monkey after some rounds",
+                run: state,
+                complete: Some(|_| vec!["worried".to_string()]),
+            },
+            Command {
+                name: "business",
+                args: "<rounds> [worried]",
+                help: "the monkey business after some rounds",
+                run: business,
+                complete: Some(|_| vec!["worried".to_string()]),
+            },
+            Command {
+                name: "monkey",
+                args: "<n>",
+                help: "the notes on monkey n",
+                run: monkey,
+                complete: None,
+            },
+        ],
+    );
+}
+
+// play_args plays the rounds asked for, `worried` plays without relief like part 2
+fn play_args(monkeys: &[Monkey], args: &[&str]) -> Result<(Vec<Monkey>, Vec<i64>), String> {
+    let rounds: usize = prompt::parse_arg(args, 0, "rounds")?;
+    let relief = match args.get(1) {
+        None => true,
+        Some(&"worried") => false,
+        Some(arg) => return Err(format!("unexpected `{}`, did you mean `worried`?", arg)),
+    };
+
+    let mut monkeys = monkeys.to_vec();
+    let inspections = play(&mut monkeys, rounds, relief)?;
+    Ok((monkeys, inspections))
+}
+
+fn state(monkeys: &[Monkey], args: &[&str]) -> Result<String, String> {
+    let (monkeys, inspections) = play_args(monkeys, args)?;
+    Ok(monkeys
+        .iter()
+        .zip(inspections)
+        .enumerate()
+        .map(|(i, (monkey, inspected))| {
+            let items: Vec<String> = monkey.items.iter().map(|w| w.to_string()).collect();
+            format!(
+                "Monkey {} ({} inspections): {}",
+                i,
+                inspected,
+                items.join(", ")
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n"))
+}
+
+fn business(monkeys: &[Monkey], args: &[&str]) -> Result<String, String> {
+    let (_, inspections) = play_args(monkeys, args)?;
+    Ok(monkey_business(&inspections).to_string())
+}
+
+fn monkey(monkeys: &[Monkey], args: &[&str]) -> Result<String, String> {
+    let n: usize = prompt::parse_arg(args, 0, "n")?;
+    let monkey = monkeys
+        .get(n)
+        .ok_or_else(|| format!("there are only monkeys 0 to {}", monkeys.len() - 1))?;
+    Ok(format!(
+        "items: {:?}\noperation: new = {}\ntest: divisible by {}, true: throw to {}, false: throw to {}",
+        monkey.items,
+        monkey.operation.join(" "),
+        monkey.test_divisor,
+        monkey.true_monkey,
+        monkey.false_monkey
+    ))
+}
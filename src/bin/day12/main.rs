@@ -22,21 +22,26 @@ For part 2, we simply take the end as the starting point.
 
 use std::{fs, time::Instant};
 
+use advent2022::{fuzz, input_path, prompt};
 use pathfinding::prelude::bfs;
 
+mod repl;
+
 const START: i64 = 'S' as i64 - 'a' as i64;
 const END: i64 = 'E' as i64 - 'a' as i64;
 
 fn main() {
+    fuzz::exit_if_requested("day12", parse_map);
+
     // let input = fs::read_to_string("input/day12-test.txt").unwrap();
-    let input = fs::read_to_string("input/day12.txt").unwrap();
-    let mut height_map: Vec<Vec<i64>> = input
-        .split("\n")
-        .map(|line| line.chars().map(|ch| ch as i64 - 'a' as i64).collect())
-        .collect();
+    let input = fs::read_to_string(input_path("day12")).unwrap();
+    let (height_map, start, end) =
+        parse_map(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
 
-    // get start and end
-    let (start, end) = clean_map(&mut height_map);
+    if prompt::requested() {
+        repl::run(&(height_map, start, end));
+        return;
+    }
 
     // Part 1
     let mut start_time = Instant::now();
@@ -55,8 +60,45 @@ fn main() {
     );
 }
 
+type HeightMap = (Vec<Vec<i64>>, (usize, usize), (usize, usize));
+
+// parse_map reads the heights and gets the start and end, which must be there exactly once
+fn parse_map(input: &str) -> Result<HeightMap, String> {
+    let lines: Vec<&str> = input.split("\n").collect();
+    for (i, line) in lines.iter().enumerate() {
+        if line.is_empty() || line.len() != lines[0].len() {
+            return Err(format!(
+                "line {}: expected {} squares",
+                i + 1,
+                lines[0].len()
+            ));
+        }
+        if let Some(ch) = line
+            .chars()
+            .find(|ch| !ch.is_ascii_lowercase() && *ch != 'S' && *ch != 'E')
+        {
+            return Err(format!("line {}: invalid elevation `{}`", i + 1, ch));
+        }
+    }
+    for mark in ['S', 'E'] {
+        if input.matches(mark).count() != 1 {
+            return Err(format!("expected exactly one `{}`", mark));
+        }
+    }
+
+    let mut height_map: Vec<Vec<i64>> = lines
+        .iter()
+        .map(|line| line.chars().map(|ch| ch as i64 - 'a' as i64).collect())
+        .collect();
+
+    // get start and end
+    let (start, end) = clean_map(&mut height_map);
+
+    Ok((height_map, start, end))
+}
+
 // clean_map gets the start and end and sets them to 0 and 25, respectively
-fn clean_map(height_map: &mut Vec<Vec<i64>>) -> ((usize, usize), (usize, usize)) {
+fn clean_map(height_map: &mut [Vec<i64>]) -> ((usize, usize), (usize, usize)) {
     let mut start = (0, 0);
     let mut end = (0, 0);
 
@@ -77,22 +119,23 @@ fn clean_map(height_map: &mut Vec<Vec<i64>>) -> ((usize, usize), (usize, usize))
     (start, end)
 }
 
-fn part_1(height_map: &Vec<Vec<i64>>, start: &(usize, usize), end: &(usize, usize)) -> i64 {
+fn part_1(height_map: &[Vec<i64>], start: &(usize, usize), end: &(usize, usize)) -> i64 {
     let success = |p: &(usize, usize)| *p == *end;
 
     let neighbors = neighbor_fn(height_map, false);
 
-    bfs(start, neighbors, success).unwrap().len() as i64
+    // the path includes the start, so it takes one step less than it has points
+    bfs(start, neighbors, success).unwrap().len() as i64 - 1
 }
 
 // neighbor_fn makes a closure that will find all possible neighbors of a point
 // it can optionally be reversed for part 2
 // it is also a totally unnecessary exploration of lifetime parameters which I have now understood better, yay!
 fn neighbor_fn<'a>(
-    height_map: &'a Vec<Vec<i64>>,
+    height_map: &'a [Vec<i64>],
     reverse: bool,
 ) -> impl Fn(&(usize, usize)) -> Vec<(usize, usize)> + 'a {
-    return move |p: &(usize, usize)| {
+    move |p: &(usize, usize)| {
         let neighbors = neighbors_of(height_map, p);
         let mut possible_neighbors = Vec::new();
 
@@ -110,12 +153,12 @@ fn neighbor_fn<'a>(
         }
 
         possible_neighbors
-    };
+    }
 }
 
 // neighbors_of gets all neighbors of p that aren't out of the bounds
 // it doesn't check the height difference
-fn neighbors_of(height_map: &Vec<Vec<i64>>, p: &(usize, usize)) -> Vec<(usize, usize)> {
+fn neighbors_of(height_map: &[Vec<i64>], p: &(usize, usize)) -> Vec<(usize, usize)> {
     let mut neighbors = Vec::new();
 
     if p.0 != 0 {
@@ -134,10 +177,11 @@ fn neighbors_of(height_map: &Vec<Vec<i64>>, p: &(usize, usize)) -> Vec<(usize, u
     neighbors
 }
 
-fn part_2(height_map: &Vec<Vec<i64>>, end: &(usize, usize)) -> i64 {
+fn part_2(height_map: &[Vec<i64>], end: &(usize, usize)) -> i64 {
     let success = |p: &(usize, usize)| height_map[p.0][p.1] == 0;
 
     bfs(end, neighbor_fn(height_map, true), success)
         .unwrap()
         .len() as i64
+        - 1
 }
@@ -0,0 +1,114 @@
+use advent2022::prompt::{self, Command};
+use pathfinding::prelude::bfs;
+
+use super::{neighbor_fn, HeightMap};
+
+pub fn run(map: &HeightMap) {
+    prompt::run(
+        "day12",
+        map,
+        &[
+            Command {
+                name: "path",
+                args: "[row col]",
+                help: "draw the shortest path to E, from S or from the given square",
+                run: path,
+                complete: None,
+            },
+            Command {
+                name: "hike",
+                args: "",
+                help: "draw the shortest path to E from any square at elevation a",
+                run: hike,
+                complete: None,
+            },
+            Command {
+                name: "height",
+                args: "<row> <col>",
+                help: "the elevation of a square",
+                run: height,
+                complete: None,
+            },
+        ],
+    );
+}
+
+fn square(map: &HeightMap, args: &[&str]) -> Result<(usize, usize), String> {
+    let row: usize = prompt::parse_arg(args, 0, "row")?;
+    let col: usize = prompt::parse_arg(args, 1, "col")?;
+    if row >= map.0.len() || col >= map.0[0].len() {
+        return Err(format!(
+            "the map is {} rows by {} columns",
+            map.0.len(),
+            map.0[0].len()
+        ));
+    }
+    Ok((row, col))
+}
+
+fn path(map: &HeightMap, args: &[&str]) -> Result<String, String> {
+    let (height_map, start, end) = map;
+    let from = if args.is_empty() {
+        *start
+    } else {
+        square(map, args)?
+    };
+
+    let steps = bfs(&from, neighbor_fn(height_map, false), |p| p == end)
+        .ok_or_else(|| format!("E can't be reached from {:?}", from))?;
+    Ok(draw(map, &steps))
+}
+
+fn hike(map: &HeightMap, _: &[&str]) -> Result<String, String> {
+    let (height_map, _, end) = map;
+
+    // search back from E like part 2 does, and then turn the path around
+    let mut steps = bfs(end, neighbor_fn(height_map, true), |p| {
+        height_map[p.0][p.1] == 0
+    })
+    .ok_or("E can't be reached from any square at elevation a")?;
+    steps.reverse();
+    Ok(draw(map, &steps))
+}
+
+fn height(map: &HeightMap, args: &[&str]) -> Result<String, String> {
+    let (row, col) = square(map, args)?;
+    Ok(letter(map.0[row][col]).to_string())
+}
+
+fn letter(height: i64) -> char {
+    (b'a' + height as u8) as char
+}
+
+// draw shows the path with arrows on top of the map, everything else is dimmed to dots
+fn draw(map: &HeightMap, steps: &[(usize, usize)]) -> String {
+    let (height_map, _, end) = map;
+    let mut grid: Vec<Vec<char>> = height_map
+        .iter()
+        .map(|row| row.iter().map(|_| '.').collect())
+        .collect();
+
+    for step in steps.windows(2) {
+        let (a, b) = (step[0], step[1]);
+        grid[a.0][a.1] = match (b.0 as i64 - a.0 as i64, b.1 as i64 - a.1 as i64) {
+            (-1, _) => '^',
+            (1, _) => 'v',
+            (_, -1) => '<',
+            _ => '>',
+        };
+    }
+    grid[end.0][end.1] = 'E';
+
+    let from = steps[0];
+    let mut lines: Vec<String> = grid
+        .into_iter()
+        .map(|row| row.into_iter().collect())
+        .collect();
+    lines.push(format!(
+        "{} steps from {:?} at elevation {}",
+        steps.len() - 1,
+        from,
+        letter(height_map[from.0][from.1])
+    ));
+    lines.join("\n")
+}
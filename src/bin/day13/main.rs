@@ -1,4 +1,11 @@
-use std::{cmp::Ordering, fs, time::Instant};
+use std::{cmp::Ordering, fmt, fs, slice, time::Instant};
+
+use advent2022::{fuzz, input_path, prompt};
+
+mod repl;
+
+// way deeper than any real packet
+const MAX_DEPTH: usize = 100;
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum Value {
@@ -8,38 +15,44 @@ enum Value {
 
 impl Value {
     // What do you mean the input lines are already valid json????????? Homemade parser it is
-    fn parse_str(input: &str) -> Value {
+    fn parse_str(input: &str) -> Result<Value, String> {
         let chars: Vec<char> = input.chars().collect();
 
-        Self::parse_value(&chars).0
+        let (value, n) = Self::parse_value(&chars, 0)?;
+        match chars.get(n) {
+            Some(ch) => Err(format!("unexpected `{}` after packet", ch)),
+            None => Ok(value),
+        }
     }
 
     // this is not meant to be accessed directly, recursive parser that parse_str uses.
     // it returns the number of characters read from chars, so that recursing calls can skip them.
     // there is undubitably a better way but I don't know it!
-    fn parse_value(chars: &[char]) -> (Value, usize) {
-        match chars[0] {
-            '[' => {
+    fn parse_value(chars: &[char], depth: usize) -> Result<(Value, usize), String> {
+        match chars.first() {
+            Some('[') => {
+                // comparing recurses just as deep as parsing, so don't let the stack overflow
+                if depth == MAX_DEPTH {
+                    return Err(format!("lists nested deeper than {}", MAX_DEPTH));
+                }
+
                 // list - parse until closing `]`
                 let mut list = Vec::new();
                 let mut i = 1;
                 loop {
-                    if chars.len() == i {
-                        panic!("missing `]` in input");
-                    }
-
-                    match chars[i] {
-                        ']' => {
+                    match chars.get(i) {
+                        None => return Err("missing `]` in input".to_string()),
+                        Some(']') => {
                             // end of list found
-                            return (Self::List(list), i + 1);
+                            return Ok((Self::List(list), i + 1));
                         }
-                        ',' => {
+                        Some(',') => {
                             // next value
                             i += 1;
                         }
-                        _ => {
+                        Some(_) => {
                             // a value is encountered, parse it and move the cursor
-                            let (v, n) = Self::parse_value(&chars[i..chars.len()]);
+                            let (v, n) = Self::parse_value(&chars[i..], depth + 1)?;
                             list.push(v);
                             i += n;
                         }
@@ -48,18 +61,23 @@ impl Value {
             }
 
             // number, use hacky ASCII for fast parsing
-            n => {
-                let mut v = n as i64 - '0' as i64;
-
-                let mut j = 1;
-                while chars[j].is_numeric() {
-                    v *= 10;
-                    v += chars[j] as i64 - '0' as i64;
+            Some(n) if n.is_ascii_digit() => {
+                let mut v: i64 = 0;
+
+                let mut j = 0;
+                while let Some(digit) = chars.get(j).filter(|ch| ch.is_ascii_digit()) {
+                    v = v
+                        .checked_mul(10)
+                        .and_then(|v| v.checked_add(*digit as i64 - '0' as i64))
+                        .ok_or_else(|| "number too large".to_string())?;
                     j += 1;
                 }
 
-                (Self::Int(v), j)
+                Ok((Self::Int(v), j))
             }
+
+            Some(ch) => Err(format!("unexpected `{}`", ch)),
+            None => Err("unexpected end of packet".to_string()),
         }
     }
 
@@ -68,10 +86,10 @@ impl Value {
         match left {
             Value::List(left) => match right {
                 Value::List(right) => Self::compare_lists(left, right),
-                Value::Int(_) => Self::compare_lists(left, &vec![right.clone()]),
+                Value::Int(_) => Self::compare_lists(left, slice::from_ref(right)),
             },
             Value::Int(left_int) => match right {
-                Value::List(right) => Self::compare_lists(&vec![left.clone()], right),
+                Value::List(right) => Self::compare_lists(slice::from_ref(left), right),
                 Value::Int(right) => {
                     let diff = left_int - right;
                     if diff < 0 {
@@ -87,7 +105,7 @@ impl Value {
     }
 
     // not meant to be used directly, used by compare
-    fn compare_lists(left: &Vec<Value>, right: &Vec<Value>) -> Ordering {
+    fn compare_lists(left: &[Value], right: &[Value]) -> Ordering {
         let mut i = 0;
         loop {
             if left.len() == i && right.len() == i {
@@ -124,30 +142,77 @@ impl PartialOrd for Value {
     }
 }
 
+// prints packets back the way they are written in the input
+impl fmt::Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Value::Int(v) => write!(f, "{}", v),
+            Value::List(list) => {
+                write!(f, "[")?;
+                for (i, v) in list.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, "{}", v)?;
+                }
+                write!(f, "]")
+            }
+        }
+    }
+}
+
 fn main() {
+    fuzz::exit_if_requested("day13", |input: &str| {
+        parse_pairs(input).and(parse_packets(input))
+    });
+
     // let input = fs::read_to_string("input/day13-test.txt").unwrap();
-    let input = fs::read_to_string("input/day13.txt").unwrap();
+    let input = fs::read_to_string(input_path("day13")).unwrap();
+    let pairs = parse_pairs(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
+    let packets = parse_packets(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
+
+    if prompt::requested() {
+        repl::run(&packets);
+        return;
+    }
 
     // time that stuff!
     let mut start_time = Instant::now();
-    println!("Part 1 - {} ({:?})", part_1(&input), start_time.elapsed());
+    println!("Part 1 - {} ({:?})", part_1(&pairs), start_time.elapsed());
     start_time = Instant::now();
-    println!("Part 2 - {} ({:?})", part_2(&input), start_time.elapsed());
+    println!("Part 2 - {} ({:?})", part_2(packets), start_time.elapsed());
 }
 
-fn part_1(input: &String) -> usize {
-    // parse each pair of packets
-    let pairs: Vec<(Value, Value)> = input
+// parse_pairs parses each pair of packets
+fn parse_pairs(input: &str) -> Result<Vec<(Value, Value)>, String> {
+    input
         .split("\n\n")
-        .map(|pair_str| {
-            let mut parsed = pair_str
+        .enumerate()
+        .map(|(i, pair_str)| {
+            let packets = pair_str
                 .split("\n")
-                .map(|pair| (Value::parse_str(pair)))
-                .into_iter();
-            (parsed.next().unwrap(), parsed.next().unwrap())
+                .map(Value::parse_str)
+                .collect::<Result<Vec<Value>, String>>()
+                .map_err(|e| format!("pair {}: {}", i + 1, e))?;
+            match <[Value; 2]>::try_from(packets) {
+                Ok([left, right]) => Ok((left, right)),
+                Err(_) => Err(format!("pair {}: expected two packets", i + 1)),
+            }
         })
-        .collect();
+        .collect()
+}
 
+// parse_packets parses all packets, rejecting empty lines
+fn parse_packets(input: &str) -> Result<Vec<Value>, String> {
+    input
+        .split("\n")
+        .enumerate()
+        .filter(|(_, line)| line != &"")
+        .map(|(i, line)| Value::parse_str(line).map_err(|e| format!("line {}: {}", i + 1, e)))
+        .collect()
+}
+
+fn part_1(pairs: &[(Value, Value)]) -> usize {
     // map to index + 1 ONLY if ordered correctly, otherwise 0, then sum
     // we implement Ord so we can just use <
     pairs
@@ -157,17 +222,10 @@ fn part_1(input: &String) -> usize {
         .sum()
 }
 
-fn part_2(input: &String) -> usize {
-    // reject empty lines during parsing
-    let mut packets: Vec<Value> = input
-        .split("\n")
-        .filter(|line| line != &"")
-        .map(|line| Value::parse_str(line))
-        .collect();
-
+fn part_2(mut packets: Vec<Value>) -> usize {
     // add dividers
-    let divider_1 = Value::parse_str("[[2]]");
-    let divider_2 = Value::parse_str("[[6]]");
+    let divider_1 = Value::parse_str("[[2]]").unwrap();
+    let divider_2 = Value::parse_str("[[6]]").unwrap();
     packets.push(divider_1.clone());
     packets.push(divider_2.clone());
 
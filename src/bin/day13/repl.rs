@@ -0,0 +1,103 @@
+use std::cmp::Ordering;
+
+use advent2022::prompt::{self, Command};
+
+use super::Value;
+
+pub fn run(packets: &[Value]) {
+    prompt::run(
+        "day13",
+        packets,
+        &[
+            Command {
+                name: "show",
+                args: "<n>",
+                help: "print packet n, counting from 1 and skipping empty lines",
+                run: show,
+                complete: None,
+            },
+            Command {
+                name: "compare",
+                args: "<a> <b>",
+                help: "compare packets a and b",
+                run: compare,
+                complete: None,
+            },
+            Command {
+                name: "pair",
+                args: "<n>",
+                help: "compare the two packets of pair n",
+                run: pair,
+                complete: None,
+            },
+            Command {
+                name: "rank",
+                args: "<n>",
+                help: "where packet n ends up after sorting, dividers included",
+                run: rank,
+                complete: None,
+            },
+        ],
+    );
+}
+
+// packet reads argument i as a packet number, packets are numbered from 1
+fn packet<'a>(
+    packets: &'a [Value],
+    args: &[&str],
+    i: usize,
+    name: &str,
+) -> Result<(usize, &'a Value), String> {
+    let n: usize = prompt::parse_arg(args, i, name)?;
+    match n.checked_sub(1).and_then(|i| packets.get(i)) {
+        Some(packet) => Ok((n, packet)),
+        None => Err(format!("there are only {} packets", packets.len())),
+    }
+}
+
+fn show(packets: &[Value], args: &[&str]) -> Result<String, String> {
+    let (_, packet) = packet(packets, args, 0, "n")?;
+    Ok(packet.to_string())
+}
+
+fn compare(packets: &[Value], args: &[&str]) -> Result<String, String> {
+    let (a, left) = packet(packets, args, 0, "a")?;
+    let (b, right) = packet(packets, args, 1, "b")?;
+    Ok(describe(a, left, b, right))
+}
+
+fn pair(packets: &[Value], args: &[&str]) -> Result<String, String> {
+    let n: usize = prompt::parse_arg(args, 0, "n")?;
+    if n == 0 || 2 * n > packets.len() {
+        return Err(format!("there are only {} pairs", packets.len() / 2));
+    }
+    Ok(describe(
+        2 * n - 1,
+        &packets[2 * n - 2],
+        2 * n,
+        &packets[2 * n - 1],
+    ))
+}
+
+fn describe(a: usize, left: &Value, b: usize, right: &Value) -> String {
+    let verdict = match left.cmp(right) {
+        Ordering::Less => format!("{} < {}, right order", a, b),
+        Ordering::Greater => format!("{} > {}, wrong order", a, b),
+        Ordering::Equal => format!("{} = {}", a, b),
+    };
+    format!("{}: {}\n{}: {}\n{}", a, left, b, right, verdict)
+}
+
+fn rank(packets: &[Value], args: &[&str]) -> Result<String, String> {
+    let (_, packet) = packet(packets, args, 0, "n")?;
+    let dividers = [
+        Value::parse_str("[[2]]").unwrap(),
+        Value::parse_str("[[6]]").unwrap(),
+    ];
+    let before = packets
+        .iter()
+        .chain(dividers.iter())
+        .filter(|p| *p < packet)
+        .count();
+    Ok(format!("position {} of {}", before + 1, packets.len() + 2))
+}
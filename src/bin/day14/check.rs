@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use advent2022::{
+    prop::{self, Config},
+    rng::Rng,
+};
+
+use super::{map_rocks, parse_paths, part_1, part_2};
+
+type Path = Vec<(i64, i64)>;
+
+// run compares both parts against a plain grain-by-grain simulation
+pub fn run(config: &Config) -> bool {
+    let part_1_ok = prop::check(
+        "day14 part_1 matches simulation",
+        config,
+        generate,
+        |paths| shrink(paths),
+        |paths| {
+            if paths.is_empty() {
+                return Ok(());
+            }
+            // when the sand piles up to the source, part 1 never ends,
+            // which real inputs don't do, so there is nothing to compare
+            let slow = match simulate(paths, false) {
+                Some(slow) => slow,
+                None => return Ok(()),
+            };
+            let (rocks, lowest) = map_rocks(&parse_paths(&to_input(paths))?);
+            expect_eq(part_1(&rocks, lowest), slow)
+        },
+    );
+
+    let part_2_ok = prop::check(
+        "day14 part_2 pyramid fill matches simulation",
+        config,
+        generate,
+        |paths| shrink(paths),
+        |paths| {
+            if paths.is_empty() {
+                return Ok(());
+            }
+            let (rocks, lowest) = map_rocks(&parse_paths(&to_input(paths))?);
+            expect_eq(part_2(&rocks, lowest), simulate(paths, true).unwrap())
+        },
+    );
+
+    part_1_ok && part_2_ok
+}
+
+fn expect_eq(fast: i64, slow: i64) -> Result<(), String> {
+    if fast == slow {
+        Ok(())
+    } else {
+        Err(format!("solver says {}, simulation says {}", fast, slow))
+    }
+}
+
+// a few short axis aligned rock paths around the sand source
+fn generate(rng: &mut Rng, size: usize) -> Vec<Path> {
+    let spread = 2 + size as i64 / 3;
+    let depth = 2 + size as i64 / 2;
+
+    (0..rng.range(1, 1 + size as i64 / 5))
+        .map(|_| {
+            let mut p = (rng.range(500 - spread, 500 + spread), rng.range(1, depth));
+            let mut path = vec![p];
+            for _ in 0..rng.range(1, 3) {
+                let step = rng.range(1, spread) * if rng.chance(0.5) { 1 } else { -1 };
+                p = if rng.chance(0.5) {
+                    (p.0 + step, p.1)
+                } else {
+                    (p.0, (p.1 + step).max(1))
+                };
+                if p != *path.last().unwrap() {
+                    path.push(p);
+                }
+            }
+            path
+        })
+        .filter(|path| path.len() > 1)
+        .collect()
+}
+
+// paths only lose points at their ends and move as a whole, so they stay axis aligned
+fn shrink(paths: &[Path]) -> Vec<Vec<Path>> {
+    prop::shrink_vec(paths, |path| {
+        let mut smaller = Vec::new();
+        if path.len() > 2 {
+            smaller.push(path[1..].to_vec());
+            smaller.push(path[..path.len() - 1].to_vec());
+        }
+        if path.iter().all(|p| p.1 > 1) {
+            smaller.push(path.iter().map(|p| (p.0, p.1 - 1)).collect());
+        }
+        for dx in [-1, 1] {
+            if path
+                .iter()
+                .all(|p| (p.0 - 500).abs() > (p.0 + dx - 500).abs())
+            {
+                smaller.push(path.iter().map(|p| (p.0 + dx, p.1)).collect());
+            }
+        }
+        smaller
+    })
+}
+
+fn to_input(paths: &[Path]) -> String {
+    paths
+        .iter()
+        .map(|path| {
+            path.iter()
+                .map(|(x, y)| format!("{},{}", x, y))
+                .collect::<Vec<String>>()
+                .join(" -> ")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// simulate drops sand one grain at a time until it falls into the abyss,
+// or with a floor, until the source is blocked.
+// Without a floor a blocked source means sand never reaches the abyss, so it returns None
+fn simulate(paths: &[Path], floor: bool) -> Option<i64> {
+    let mut blocked = HashSet::new();
+    for path in paths {
+        for pair in path.windows(2) {
+            let (a, b) = (pair[0], pair[1]);
+            for x in a.0.min(b.0)..=a.0.max(b.0) {
+                for y in a.1.min(b.1)..=a.1.max(b.1) {
+                    blocked.insert((x, y));
+                }
+            }
+        }
+    }
+    let lowest = blocked.iter().map(|p| p.1).max().unwrap_or(0);
+    let floor_y = lowest + 2;
+
+    let mut grains = 0;
+    while !blocked.contains(&(500, 0)) {
+        let mut p = (500, 0);
+        loop {
+            if !floor && p.1 > lowest {
+                return Some(grains);
+            }
+
+            let next = [(p.0, p.1 + 1), (p.0 - 1, p.1 + 1), (p.0 + 1, p.1 + 1)]
+                .into_iter()
+                .find(|n| !(blocked.contains(n) || floor && n.1 == floor_y));
+            match next {
+                Some(n) => p = n,
+                None => break,
+            }
+        }
+
+        blocked.insert(p);
+        grains += 1;
+    }
+
+    if floor {
+        Some(grains)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_parts_match_simulation() {
+        let config = Config {
+            cases: 200,
+            seed: 2022,
+            max_size: 30,
+        };
+        assert!(run(&config));
+    }
+}
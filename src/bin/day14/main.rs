@@ -1,13 +1,30 @@
-use std::{collections::HashSet, fs, time::Instant};
+use std::{collections::HashSet, env, fs, process, time::Instant};
+
+use advent2022::{fuzz, input_path, prop};
+
+mod check;
+mod vis;
 
 fn main() {
+    if let Some(config) = prop::requested() {
+        process::exit(if check::run(&config) { 0 } else { 1 });
+    }
+    fuzz::exit_if_requested("day14", parse_paths);
+
     // let input = fs::read_to_string("input/day14-test.txt").unwrap();
-    let input = fs::read_to_string("input/day14.txt").unwrap();
+    let input = fs::read_to_string(input_path("day14")).unwrap();
 
     let total_time = Instant::now();
 
     // parse into a hash set of rocks
-    let (rocks, lowest) = map_rocks(input);
+    let paths = parse_paths(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
+    let (rocks, lowest) = map_rocks(&paths);
+
+    // `day14 vis` shows the sand falling instead of counting it
+    if env::args().nth(1).is_some_and(|a| a == "vis") {
+        vis::run(&rocks, lowest).unwrap_or_else(|e| panic!("{}", e));
+        return;
+    }
 
     let t = Instant::now();
     println!("Part 1 - {} ({:?})", part_1(&rocks, lowest), t.elapsed());
@@ -18,23 +35,66 @@ fn main() {
     println!("Total execution time: {:?}", total_time.elapsed());
 }
 
-fn map_rocks(input: String) -> (HashSet<(i64, i64)>, i64) {
+// keeps the maps small, real inputs stay well below this
+const MAX_COORDINATE: i64 = 10_000;
+
+fn parse_paths(input: &str) -> Result<Vec<Vec<(i64, i64)>>, String> {
     // first split into lines and pairs of i64
-    let rock_lines = input.split("\n").map(|line| {
-        line.split(" -> ").map(|p| {
-            // split by comma and parse values
-            let mut values = p.split(",").map(|v| v.parse().unwrap());
-            (values.next().unwrap(), values.next().unwrap())
+    input
+        .split("\n")
+        .enumerate()
+        .map(|(i, line)| {
+            let path = line
+                .split(" -> ")
+                .map(|p| {
+                    // split by comma and parse values
+                    let (x, y) = p
+                        .split_once(",")
+                        .ok_or_else(|| format!("invalid point `{}`", p))?;
+                    let point: (i64, i64) = (
+                        x.parse().map_err(|_| format!("invalid x `{}`", x))?,
+                        y.parse().map_err(|_| format!("invalid y `{}`", y))?,
+                    );
+                    if !(0..=MAX_COORDINATE).contains(&point.0)
+                        || !(0..=MAX_COORDINATE).contains(&point.1)
+                    {
+                        return Err(format!("point `{}` is out of range", p));
+                    }
+                    Ok(point)
+                })
+                .collect::<Result<Vec<(i64, i64)>, String>>()
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+
+            // map_rocks walks from point to point, which never ends on a diagonal
+            if path
+                .windows(2)
+                .any(|w| w[0].0 != w[1].0 && w[0].1 != w[1].1)
+            {
+                return Err(format!("line {}: rock lines must be straight", i + 1));
+            }
+            Ok(path)
         })
-    });
+        .collect()
+}
 
+fn map_rocks(paths: &[Vec<(i64, i64)>]) -> (HashSet<(i64, i64)>, i64) {
     // this will hold our rocks
     let mut rocks: HashSet<(i64, i64)> = HashSet::with_capacity(800); // seems to be enough preallocated space to prevent reallocating
     let mut lowest = 0;
 
-    for mut rock_line in rock_lines {
+    for path in paths {
+        let mut rock_line = path.iter().copied();
+
         // starting point
-        let mut cursor: (i64, i64) = rock_line.next().unwrap();
+        let mut cursor: (i64, i64) = match rock_line.next() {
+            Some(p) => p,
+            None => continue,
+        };
+
+        // the starting point can be the lowest rock too, if the path goes up
+        if cursor.1 > lowest {
+            lowest = cursor.1;
+        }
 
         for p in rock_line {
             // one step towards p
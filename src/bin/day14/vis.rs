@@ -0,0 +1,237 @@
+use std::{collections::HashSet, time::Duration};
+
+use ratatui::{
+    crossterm::event::{self, Event, KeyCode, KeyEventKind},
+    layout::{Constraint, Layout},
+    style::{Color, Style},
+    text::{Line, Span},
+    widgets::{Block, Paragraph},
+    DefaultTerminal, Frame,
+};
+
+// The sand falling into the cave, one grain at a time: `day14 vis`.
+// Every terminal cell shows two points of the cave stacked on top of each other, so the cave
+// fits in half the rows. The view follows the falling grain until it is scrolled by hand.
+
+const KEYS: &str = "space pause  +/- speed  ↑/↓ scroll  f follow  tab part 1/2  q quit";
+
+struct Cave<'a> {
+    rocks: &'a HashSet<(i64, i64)>,
+    lowest: i64,
+    // part 2 has a floor two below the lowest rock, part 1 lets the sand fall into the abyss
+    floor: bool,
+    sand: HashSet<(i64, i64)>,
+    grain: Option<(i64, i64)>,
+    done: bool,
+    // the columns the view has to show, which grow as the sand spreads
+    left: i64,
+    right: i64,
+}
+
+impl Cave<'_> {
+    fn new(rocks: &HashSet<(i64, i64)>, lowest: i64, floor: bool) -> Cave<'_> {
+        let left = rocks.iter().map(|p| p.0).min().unwrap_or(500).min(500) - 1;
+        let right = rocks.iter().map(|p| p.0).max().unwrap_or(500).max(500) + 1;
+        Cave {
+            rocks,
+            lowest,
+            floor,
+            sand: HashSet::new(),
+            grain: None,
+            done: false,
+            left,
+            right,
+        }
+    }
+
+    fn blocked(&self, p: (i64, i64)) -> bool {
+        (self.floor && p.1 == self.lowest + 2) || self.rocks.contains(&p) || self.sand.contains(&p)
+    }
+
+    // step moves the falling grain one point, or lets a new one in at 500,0
+    fn step(&mut self) {
+        if self.done {
+            return;
+        }
+        let Some(p) = self.grain else {
+            if self.blocked((500, 0)) {
+                self.done = true;
+            } else {
+                self.grain = Some((500, 0));
+            }
+            return;
+        };
+
+        // without a floor the first grain that falls past the lowest rock falls forever
+        if !self.floor && p.1 == self.lowest {
+            self.grain = None;
+            self.done = true;
+            return;
+        }
+        match [(p.0, p.1 + 1), (p.0 - 1, p.1 + 1), (p.0 + 1, p.1 + 1)]
+            .into_iter()
+            .find(|next| !self.blocked(*next))
+        {
+            Some(next) => self.grain = Some(next),
+            None => {
+                self.sand.insert(p);
+                self.left = self.left.min(p.0 - 1);
+                self.right = self.right.max(p.0 + 1);
+                self.grain = None;
+            }
+        }
+    }
+
+    fn color(&self, p: (i64, i64)) -> Color {
+        if self.grain == Some(p) {
+            Color::LightRed
+        } else if self.sand.contains(&p) {
+            Color::Yellow
+        } else if self.blocked(p) {
+            Color::Gray
+        } else {
+            Color::Black
+        }
+    }
+}
+
+struct View<'a> {
+    cave: Cave<'a>,
+    paused: bool,
+    // grain steps per frame
+    speed: usize,
+    follow: bool,
+    // the first cave row on screen, always even
+    top: i64,
+}
+
+impl View<'_> {
+    fn draw(&mut self, frame: &mut Frame) {
+        let [title, area, footer] = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Min(3),
+            Constraint::Length(1),
+        ])
+        .areas(frame.area());
+
+        let cave = &self.cave;
+        let state = match (cave.done, self.paused) {
+            (true, _) => "done",
+            (false, true) => "paused",
+            (false, false) => "falling",
+        };
+        frame.render_widget(
+            Line::from(format!(
+                "Part {} - {} grains of sand at rest, {}, {} steps a frame",
+                if cave.floor { 2 } else { 1 },
+                cave.sand.len(),
+                state,
+                self.speed
+            )),
+            title,
+        );
+
+        let block = Block::bordered();
+        let inner = block.inner(area);
+        let (width, height) = (inner.width as i64, inner.height as i64);
+
+        // keep the falling grain on screen, and never scroll past the bottom of the cave
+        let bottom = cave.lowest + 2;
+        if let (true, Some(grain)) = (self.follow, cave.grain) {
+            if grain.1 < self.top || grain.1 >= self.top + 2 * height {
+                self.top = grain.1 - height;
+            }
+        }
+        self.top = self.top.min(bottom + 1 - 2 * height).max(0) / 2 * 2;
+
+        // when the cave is wider than the screen, the middle of it around 500 is shown
+        let left = match cave.right - cave.left + 1 > width {
+            true => (500 - width / 2).clamp(cave.left, cave.right + 1 - width),
+            false => cave.left,
+        };
+
+        let lines: Vec<Line> = (0..height)
+            .map(|row| {
+                let y = self.top + 2 * row;
+                Line::from(
+                    (left..(left + width).min(cave.right + 1))
+                        .map(|x| {
+                            let style = Style::new()
+                                .fg(cave.color((x, y)))
+                                .bg(cave.color((x, y + 1)));
+                            Span::styled("▀", style)
+                        })
+                        .collect::<Vec<Span>>(),
+                )
+            })
+            .collect();
+        frame.render_widget(Paragraph::new(lines).block(block), area);
+
+        frame.render_widget(Line::styled(KEYS, Style::new().fg(Color::DarkGray)), footer);
+    }
+}
+
+pub fn run(rocks: &HashSet<(i64, i64)>, lowest: i64) -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, rocks, lowest);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(
+    terminal: &mut DefaultTerminal,
+    rocks: &HashSet<(i64, i64)>,
+    lowest: i64,
+) -> Result<(), String> {
+    let mut view = View {
+        cave: Cave::new(rocks, lowest, false),
+        paused: false,
+        speed: 8,
+        follow: true,
+        top: 0,
+    };
+
+    loop {
+        if !view.paused {
+            for _ in 0..view.speed {
+                view.cave.step();
+            }
+        }
+
+        terminal
+            .draw(|frame| view.draw(frame))
+            .map_err(|e| e.to_string())?;
+
+        if !event::poll(Duration::from_millis(30)).map_err(|e| e.to_string())? {
+            continue;
+        }
+        let Event::Key(key) = event::read().map_err(|e| e.to_string())? else {
+            continue;
+        };
+        if key.kind != KeyEventKind::Press {
+            continue;
+        }
+
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
+            KeyCode::Char(' ') => view.paused = !view.paused,
+            KeyCode::Char('+') | KeyCode::Char('=') => view.speed = (view.speed * 2).min(1 << 16),
+            KeyCode::Char('-') => view.speed = (view.speed / 2).max(1),
+            KeyCode::Up | KeyCode::Char('k') => {
+                view.follow = false;
+                view.top -= 2;
+            }
+            KeyCode::Down | KeyCode::Char('j') => {
+                view.follow = false;
+                view.top += 2;
+            }
+            KeyCode::Char('f') => view.follow = true,
+            KeyCode::Tab => {
+                view.cave = Cave::new(rocks, lowest, !view.cave.floor);
+                view.top = 0;
+                view.follow = true;
+            }
+            _ => {}
+        }
+    }
+}
@@ -0,0 +1,194 @@
+use std::collections::HashSet;
+
+use advent2022::{
+    prop::{self, Config},
+    rng::Rng,
+};
+
+use super::{parse_input, part1, part2};
+
+// Puzzle is a small version of the real thing: a search square from 0 to size,
+// and a row to count for part 1
+#[derive(Debug, Clone)]
+struct Puzzle {
+    sensors: Vec<((i64, i64), (i64, i64))>,
+    size: i64,
+    row: i64,
+}
+
+// run compares both parts against checking every single position
+pub fn run(config: &Config) -> bool {
+    let part1_ok = prop::check(
+        "day15 part1 matches counting the row",
+        config,
+        generate,
+        shrink,
+        |puzzle| {
+            let expected = covered_on_row(puzzle)
+                .iter()
+                .filter(|x| !puzzle.sensors.iter().any(|(_, b)| *b == (**x, puzzle.row)))
+                .count() as i64;
+            let sensors = parse_input(&to_input(puzzle))?;
+            expect_eq(part1(&sensors, puzzle.row), expected)
+        },
+    );
+
+    let part2_ok = prop::check(
+        "day15 part2 formulas find the only uncovered spot",
+        config,
+        generate,
+        shrink,
+        |puzzle| {
+            // the puzzle promises exactly one spot, anything else is not a valid input
+            let spots = uncovered(puzzle);
+            if spots.len() != 1 {
+                return Ok(());
+            }
+
+            let expected = 4_000_000 * spots[0].0 + spots[0].1;
+            let sensors = parse_input(&to_input(puzzle))?;
+            expect_eq(part2(&sensors, puzzle.size), expected)
+        },
+    );
+
+    part1_ok && part2_ok
+}
+
+fn expect_eq(fast: i64, slow: i64) -> Result<(), String> {
+    if fast == slow {
+        Ok(())
+    } else {
+        Err(format!("solver says {}, brute force says {}", fast, slow))
+    }
+}
+
+fn manhattan(a: (i64, i64), b: (i64, i64)) -> i64 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
+}
+
+// generate hides a distress beacon and keeps adding sensors with the largest range
+// that leaves it uncovered, until it is the only uncovered spot in the square
+fn generate(rng: &mut Rng, size: usize) -> Puzzle {
+    let size = 4 + size as i64;
+    let distress = (rng.range(0, size), rng.range(0, size));
+    // the row of the distress beacon has a gap in it, which part 1 has to count around
+    let row = match rng.chance(0.5) {
+        true => distress.1,
+        false => rng.range(0, size),
+    };
+    let mut puzzle = Puzzle {
+        sensors: Vec::new(),
+        size,
+        row,
+    };
+
+    while puzzle.sensors.len() < 100 {
+        let spots: Vec<(i64, i64)> = uncovered(&puzzle)
+            .into_iter()
+            .filter(|p| *p != distress)
+            .collect();
+        if spots.is_empty() {
+            break;
+        }
+
+        // right next to the distress beacon a sensor can't have any range,
+        // so cover those spots from one step further away
+        let spot = *rng.pick(&spots);
+        let sensor = if manhattan(spot, distress) == 1 {
+            (2 * spot.0 - distress.0, 2 * spot.1 - distress.1)
+        } else {
+            spot
+        };
+
+        let r = manhattan(sensor, distress) - 1;
+        let dx = rng.range(-r, r);
+        let dy = if rng.chance(0.5) {
+            r - dx.abs()
+        } else {
+            dx.abs() - r
+        };
+        puzzle
+            .sensors
+            .push((sensor, (sensor.0 + dx, sensor.1 + dy)));
+    }
+
+    puzzle
+}
+
+fn shrink(puzzle: &Puzzle) -> Vec<Puzzle> {
+    let mut smaller: Vec<Puzzle> = prop::shrink_vec(&puzzle.sensors, |_| Vec::new())
+        .into_iter()
+        .map(|sensors| Puzzle {
+            sensors,
+            ..puzzle.clone()
+        })
+        .collect();
+
+    for row in prop::shrink_int(puzzle.row, 0) {
+        smaller.push(Puzzle {
+            row,
+            ..puzzle.clone()
+        });
+    }
+
+    smaller
+}
+
+fn to_input(puzzle: &Puzzle) -> String {
+    puzzle
+        .sensors
+        .iter()
+        .map(|(s, b)| {
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                s.0, s.1, b.0, b.1
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+fn covered(puzzle: &Puzzle, p: (i64, i64)) -> bool {
+    puzzle
+        .sensors
+        .iter()
+        .any(|(s, b)| manhattan(*s, p) <= manhattan(*s, *b))
+}
+
+fn uncovered(puzzle: &Puzzle) -> Vec<(i64, i64)> {
+    let mut spots = Vec::new();
+    for x in 0..=puzzle.size {
+        for y in 0..=puzzle.size {
+            if !covered(puzzle, (x, y)) {
+                spots.push((x, y));
+            }
+        }
+    }
+    spots
+}
+
+fn covered_on_row(puzzle: &Puzzle) -> HashSet<i64> {
+    let mut xs = HashSet::new();
+    for (s, b) in &puzzle.sensors {
+        let spread = manhattan(*s, *b) - (s.1 - puzzle.row).abs();
+        for x in s.0 - spread..=s.0 + spread {
+            xs.insert(x);
+        }
+    }
+    xs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn both_parts_match_checking_every_position() {
+        let config = Config {
+            cases: 200,
+            seed: 2022,
+            max_size: 30,
+        };
+        assert!(run(&config));
+    }
+}
@@ -1,4 +1,8 @@
-use std::{collections::HashSet, fs, time::Instant};
+use std::{collections::HashSet, env, fs, process, time::Instant};
+
+use advent2022::{fuzz, input_path, interval::IntervalSet, prop};
+
+mod check;
 
 struct Position {
     x: i64,
@@ -12,16 +16,30 @@ struct Sensor {
 }
 
 fn main() {
-    // let input = fs::read_to_string("input/day15-test.txt").unwrap();
-    // let y_test = 10;
-    // let max_distress = 20;
-    let input = fs::read_to_string("input/day15.txt").unwrap();
-    let y_test = 2_000_000;
-    let max_distress = 4_000_000;
+    if let Some(config) = prop::requested() {
+        process::exit(if check::run(&config) { 0 } else { 1 });
+    }
+    fuzz::exit_if_requested("day15", parse_input);
+
+    // `--row N` is the row part 1 counts, 2000000, and `--size N` the side of the square
+    // part 2 searches, 4000000. The sample asks about row 10 in a square of 20
+    let (mut y_test, mut max_distress) = (2_000_000, 4_000_000);
+    let mut args = env::args().skip(1);
+    while let Some(arg) = args.next() {
+        let usage = "usage: day15 [--row N] [--size N]";
+        let value = args.next().and_then(|v| v.parse::<i64>().ok());
+        match (arg.as_str(), value) {
+            ("--row", Some(row)) if row.abs() <= MAX_COORDINATE => y_test = row,
+            ("--size", Some(size)) if (0..=MAX_COORDINATE).contains(&size) => max_distress = size,
+            _ => panic!("{}", usage),
+        }
+    }
+
+    let input = fs::read_to_string(input_path("day15")).unwrap();
 
     let t = Instant::now();
 
-    let sensors = parse_input(input);
+    let sensors = parse_input(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
     println!("Parsing took {:?}", t.elapsed());
 
     let mut time = Instant::now();
@@ -41,40 +59,58 @@ fn main() {
     println!("Completed in {:?}", t.elapsed());
 }
 
-fn parse_input(input: String) -> Vec<Sensor> {
+// distances get added up a lot, this keeps them far away from overflowing
+const MAX_COORDINATE: i64 = 1_000_000_000;
+
+fn parse_input(input: &str) -> Result<Vec<Sensor>, String> {
     input
         .split("\n")
-        .map(|line| {
-            // do some splitting and indexing to extract numbers only
-            let (left, right) = split_two(line, ":");
-            let (s1, s2) = split_two(&left[12..left.len()], ", y=");
-            let (b1, b2) = split_two(&right[24..right.len()], ", y=");
-
-            // parse it all
-            let mut s = Sensor {
-                pos: Position {
-                    x: s1.parse().unwrap(),
-                    y: s2.parse().unwrap(),
-                },
-                nearest_beacon: Position {
-                    x: b1.parse().unwrap(),
-                    y: b2.parse().unwrap(),
-                },
-                distance: 0,
-            };
-
-            // precalculate distance because we need it a lot later
-            s.distance = manhattan(&s.pos, &s.nearest_beacon);
-
-            s
-        })
+        .enumerate()
+        .map(|(i, line)| parse_sensor(line).map_err(|e| format!("line {}: {}", i + 1, e)))
         .collect()
 }
 
+fn parse_sensor(line: &str) -> Result<Sensor, String> {
+    // do some splitting and stripping to extract numbers only
+    let (left, right) = split_two(line, ": closest beacon is at ")?;
+    let left = left
+        .strip_prefix("Sensor at ")
+        .ok_or_else(|| "expected `Sensor at`".to_string())?;
+
+    let mut s = Sensor {
+        pos: parse_position(left)?,
+        nearest_beacon: parse_position(right)?,
+        distance: 0,
+    };
+
+    // precalculate distance because we need it a lot later
+    s.distance = manhattan(&s.pos, &s.nearest_beacon);
+
+    Ok(s)
+}
+
+// parse_position parses `x=1, y=2`
+fn parse_position(input: &str) -> Result<Position, String> {
+    let (x, y) = split_two(input, ", y=")?;
+    let x = x
+        .strip_prefix("x=")
+        .ok_or_else(|| format!("expected `x=` in `{}`", input))?;
+
+    let parse = |v: &str| match v.parse::<i64>() {
+        Ok(v) if (-MAX_COORDINATE..=MAX_COORDINATE).contains(&v) => Ok(v),
+        _ => Err(format!("invalid coordinate `{}`", v)),
+    };
+    Ok(Position {
+        x: parse(x)?,
+        y: parse(y)?,
+    })
+}
+
 // helper for splitting into two strings
-fn split_two<'a>(input: &'a str, sep: &str) -> (&'a str, &'a str) {
-    let mut split = input.split(sep);
-    (split.next().unwrap(), split.next().unwrap())
+fn split_two<'a>(input: &'a str, sep: &str) -> Result<(&'a str, &'a str), String> {
+    input
+        .split_once(sep)
+        .ok_or_else(|| format!("expected `{}` in `{}`", sep.trim(), input))
 }
 
 // calculate manhattan distance
@@ -82,33 +118,30 @@ fn manhattan(a: &Position, b: &Position) -> i64 {
     (a.x - b.x).abs() + (a.y - b.y).abs()
 }
 
-fn part1(sensors: &Vec<Sensor>, y_test: i64) -> i64 {
-    let mut range = (i64::MAX, i64::MIN);
+fn part1(sensors: &[Sensor], y_test: i64) -> i64 {
     let mut beacons = HashSet::with_capacity(10); // should be enough
 
-    // we assume a continuous range based on what we know about part 2
-    for sensor in sensors {
+    // the ranges of the sensors on the row can leave gaps, so count their union
+    let covered = IntervalSet::from_ranges(sensors.iter().filter_map(|sensor| {
         // calculate how wide the range is at y
         let spread = sensor.distance - (sensor.pos.y - y_test).abs();
         if spread < 0 {
-            continue;
+            return None;
         }
 
-        // expand range
-        range.0 = range.0.min(sensor.pos.x - spread);
-        range.1 = range.1.max(sensor.pos.x + spread);
-
         // don't count beacons
         if sensor.nearest_beacon.y == y_test {
             beacons.insert(sensor.nearest_beacon.x);
         }
-    }
+        Some((sensor.pos.x - spread, sensor.pos.x + spread))
+    }));
 
-    range.1 - range.0 + 1 - beacons.len() as i64
+    covered.len() as i64 - beacons.iter().filter(|x| covered.contains(**x)).count() as i64
 }
 
 fn part2(sensors: &Vec<Sensor>, max_distress: i64) -> i64 {
     let mut p = Position { x: 0, y: 0 };
+    let mut found = false;
 
     // if there is only one spot, it is just outside the perimeter of another sensors range.
     // so we traverse the perimeters of all sensor ranges and check
@@ -121,8 +154,8 @@ fn part2(sensors: &Vec<Sensor>, max_distress: i64) -> i64 {
 
             let d = manhattan(&s1.pos, &s2.pos);
 
-            // not overlapping
-            if d >= s1.distance + s2.distance {
+            // perimeters (one step outside the ranges) not touching
+            if d > s1.distance + s2.distance + 2 {
                 continue;
             }
 
@@ -168,27 +201,36 @@ fn part2(sensors: &Vec<Sensor>, max_distress: i64) -> i64 {
                 (y_term - x_diff - d_term) / 2,
             ];
 
+            // the formulas land on the crossing of the range edges, but the perimeter
+            // is one step further out, so the spot can be off by one in either direction
             for x in x_vals {
                 for y in y_vals {
-                    p = Position { x, y };
-                    if p.x < s1.pos.x && p.x < s2.pos.x {
-                        p.x -= 1;
-                    } else if p.x < s1.pos.x && p.x < s2.pos.x {
-                        p.x += 1;
-                    } else if p.y < s1.pos.y && p.y < s2.pos.y {
-                        p.y -= 1;
-                    } else if p.y < s1.pos.y && p.y < s2.pos.y {
-                        p.y += 1;
-                    }
-
-                    if manhattan(&p, &s1.pos) != s1.distance + 1
-                        || manhattan(&p, &s2.pos) != s2.distance + 1
-                    {
-                        continue;
-                    }
-
-                    if check(sensors, &p, &max_distress) {
-                        break 'outer;
+                    for (dx, dy) in [
+                        (0, 0),
+                        (-1, 0),
+                        (1, 0),
+                        (0, -1),
+                        (0, 1),
+                        (-1, -1),
+                        (-1, 1),
+                        (1, -1),
+                        (1, 1),
+                    ] {
+                        p = Position {
+                            x: x + dx,
+                            y: y + dy,
+                        };
+
+                        if manhattan(&p, &s1.pos) != s1.distance + 1
+                            || manhattan(&p, &s2.pos) != s2.distance + 1
+                        {
+                            continue;
+                        }
+
+                        if check(sensors, &p, &max_distress) {
+                            found = true;
+                            break 'outer;
+                        }
                     }
                 }
             }
@@ -318,10 +360,48 @@ fn part2(sensors: &Vec<Sensor>, max_distress: i64) -> i64 {
         }
     }
 
+    // the spot can also be boxed in by the edge of the search area instead of a second sensor
+    if !found {
+        p = edge_spot(sensors, max_distress).expect("no spot found for the distress beacon");
+    }
+
     // calculate tuning frequency
     4_000_000 * p.x + p.y
 }
 
+// edge_spot checks the corners of the search area and everywhere a sensor's perimeter crosses its edges.
+// any spot on the edge has a covered neighbor, so it lies on the perimeter of that neighbor's sensor
+fn edge_spot(sensors: &Vec<Sensor>, max_distress: i64) -> Option<Position> {
+    let mut candidates = vec![
+        (0, 0),
+        (0, max_distress),
+        (max_distress, 0),
+        (max_distress, max_distress),
+    ];
+
+    for s in sensors {
+        let d = s.distance + 1;
+        for edge in [0, max_distress] {
+            let dx = d - (s.pos.x - edge).abs();
+            if dx >= 0 {
+                candidates.push((edge, s.pos.y - dx));
+                candidates.push((edge, s.pos.y + dx));
+            }
+
+            let dy = d - (s.pos.y - edge).abs();
+            if dy >= 0 {
+                candidates.push((s.pos.x - dy, edge));
+                candidates.push((s.pos.x + dy, edge));
+            }
+        }
+    }
+
+    candidates
+        .into_iter()
+        .map(|(x, y)| Position { x, y })
+        .find(|p| check(sensors, p, &max_distress))
+}
+
 // check if the beacon could exist at p
 fn check(sensors: &Vec<Sensor>, p: &Position, max_distress: &i64) -> bool {
     if p.x < 0 || p.y < 0 || p.x > *max_distress || p.y > *max_distress {
@@ -330,7 +410,7 @@ fn check(sensors: &Vec<Sensor>, p: &Position, max_distress: &i64) -> bool {
 
     // check if any sensor range overlaps
     for s in sensors {
-        if manhattan(&s.pos, &p) <= s.distance {
+        if manhattan(&s.pos, p) <= s.distance {
             return false;
         }
     }
@@ -1,5 +1,6 @@
 use std::{collections::HashMap, fs};
 
+use advent2022::{fuzz, input_path};
 use pathfinding::prelude::astar;
 
 type Valve = u16;
@@ -18,42 +19,19 @@ struct ValveInfo {
 }
 
 fn main() {
+    fuzz::exit_if_requested("day16", parse_valves);
+
     // let input = fs::read_to_string("input/day16-test.txt").unwrap();
-    let input = fs::read_to_string("input/day16.txt").unwrap();
+    let input = fs::read_to_string(input_path("day16")).unwrap();
 
-    let mut valves = HashMap::new();
-    input.split("\n").for_each(|line| {
-        let mut spl = line.split(" has flow rate=");
-        let b = spl.next().unwrap().as_bytes();
-        spl = spl.next().unwrap().split("; tunnel");
-        let rate_str = spl.next().unwrap();
-
-        let mut neighbor_str = spl.next().unwrap();
-        neighbor_str = neighbor_str
-            .strip_prefix("s lead to valves ")
-            .unwrap_or(neighbor_str);
-        neighbor_str = neighbor_str
-            .strip_prefix(" leads to valve ")
-            .unwrap_or(neighbor_str);
-
-        valves.insert(
-            parse_valve(&b[6..8]),
-            ValveInfo {
-                flow_rate: rate_str.parse().unwrap(),
-                neighbors: neighbor_str
-                    .split(", ")
-                    .map(|v| parse_valve(v.as_bytes()))
-                    .collect(),
-            },
-        );
-    });
+    let valves = parse_valves(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
 
     let total_flow_rate = valves.values().map(|v| v.flow_rate).sum();
 
     let start = State {
         minute: 0,
         on: Vec::new(),
-        pos: parse_valve(&['A' as u8, 'A' as u8]),
+        pos: parse_valve(b"AA"),
     };
 
     let successors = |state: &State| {
@@ -86,13 +64,83 @@ fn main() {
         neighbors
     };
 
-    let heuristic = |state: &State| h(&valves, &state, total_flow_rate);
+    let heuristic = |state: &State| h(&valves, state, total_flow_rate);
 
     let success = |state: &State| state.minute == 30;
 
     let (_, cost) = astar(&start, successors, heuristic, success).unwrap();
 
-    dbg!(total_flow_rate * 30 - cost);
+    println!("Part 1 - {}", total_flow_rate * 30 - cost);
+}
+
+fn parse_valves(input: &str) -> Result<HashMap<Valve, ValveInfo>, String> {
+    let mut valves = HashMap::new();
+    for (i, line) in input.split("\n").enumerate() {
+        let (name, info) = parse_line(line).map_err(|e| format!("line {}: {}", i + 1, e))?;
+        valves.insert(name, info);
+    }
+
+    // the search starts at AA and walks every tunnel
+    let start = parse_valve(b"AA");
+    if !valves.contains_key(&start) {
+        return Err("there is no valve AA".to_string());
+    }
+    for info in valves.values() {
+        if let Some(v) = info.neighbors.iter().find(|v| !valves.contains_key(v)) {
+            return Err(format!("tunnel to unknown valve {}", valve_name(*v)));
+        }
+    }
+
+    Ok(valves)
+}
+
+fn parse_line(line: &str) -> Result<(Valve, ValveInfo), String> {
+    let mut spl = line.split(" has flow rate=");
+    let name = spl
+        .next()
+        .and_then(|s| s.strip_prefix("Valve "))
+        .ok_or_else(|| "expected `Valve`".to_string())?;
+    spl = spl
+        .next()
+        .ok_or_else(|| "expected a flow rate".to_string())?
+        .split("; tunnel");
+    let rate_str = spl.next().unwrap_or_default();
+
+    let mut neighbor_str = spl.next().ok_or_else(|| "expected tunnels".to_string())?;
+    neighbor_str = neighbor_str
+        .strip_prefix("s lead to valves ")
+        .unwrap_or(neighbor_str);
+    neighbor_str = neighbor_str
+        .strip_prefix(" leads to valve ")
+        .unwrap_or(neighbor_str);
+
+    // flow rates get multiplied by minutes, small ones leave plenty of room
+    let flow_rate = rate_str
+        .parse::<u16>()
+        .map_err(|_| format!("invalid flow rate `{}`", rate_str))?;
+
+    Ok((
+        checked_valve(name)?,
+        ValveInfo {
+            flow_rate: flow_rate as i64,
+            neighbors: neighbor_str
+                .split(", ")
+                .map(checked_valve)
+                .collect::<Result<_, _>>()?,
+        },
+    ))
+}
+
+// checked_valve parses a valve name, which is always two capital letters
+fn checked_valve(name: &str) -> Result<Valve, String> {
+    if name.len() != 2 || !name.bytes().all(|b| b.is_ascii_uppercase()) {
+        return Err(format!("invalid valve `{}`", name));
+    }
+    Ok(parse_valve(name.as_bytes()))
+}
+
+fn valve_name(valve: Valve) -> String {
+    String::from_utf8_lossy(&valve.to_be_bytes()).to_string()
 }
 
 fn parse_valve(input: &[u8]) -> Valve {
@@ -117,7 +165,7 @@ fn h(valves: &HashMap<Valve, ValveInfo>, state: &State, total_flow_rate: i64) ->
     let mut closed_valves: Vec<Valve> = valves
         .keys()
         .filter(|k| valves.get(k).unwrap().flow_rate != 0 && !state.on.iter().any(|(v, _)| v == *k))
-        .map(|k| k.clone())
+        .copied()
         .collect();
 
     closed_valves.sort_by(|a, b| {
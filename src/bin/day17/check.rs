@@ -0,0 +1,129 @@
+use std::collections::HashSet;
+
+use advent2022::{
+    prop::{self, Config},
+    rng::Rng,
+};
+
+use super::run_game;
+
+#[derive(Debug, Clone)]
+struct Game {
+    jets: String,
+    rounds: i64,
+}
+
+// run compares the cycle extrapolation in run_game against dropping every single rock
+pub fn run(config: &Config) -> bool {
+    prop::check(
+        "day17 run_game cycle extrapolation matches simulation",
+        config,
+        generate,
+        shrink,
+        |game| {
+            if game.jets.is_empty() || game.rounds < 1 {
+                return Ok(());
+            }
+
+            let jets: Vec<char> = game.jets.chars().collect();
+            let fast = run_game(game.rounds, &jets);
+            let slow = simulate(game.rounds, &jets);
+            if fast == slow {
+                Ok(())
+            } else {
+                Err(format!("run_game says {}, simulation says {}", fast, slow))
+            }
+        },
+    )
+}
+
+// short jet patterns repeat quickly, so the cycle detection kicks in early
+fn generate(rng: &mut Rng, size: usize) -> Game {
+    Game {
+        jets: (0..rng.range(1, 1 + 3 * size as i64))
+            .map(|_| if rng.chance(0.5) { '<' } else { '>' })
+            .collect(),
+        rounds: rng.range(1, 100 + 100 * size as i64),
+    }
+}
+
+fn shrink(game: &Game) -> Vec<Game> {
+    let jets: Vec<char> = game.jets.chars().collect();
+    let mut smaller: Vec<Game> = prop::shrink_vec(&jets, |_| Vec::new())
+        .into_iter()
+        .map(|jets| Game {
+            jets: jets.into_iter().collect(),
+            rounds: game.rounds,
+        })
+        .collect();
+
+    for rounds in prop::shrink_int(game.rounds, 1) {
+        smaller.push(Game {
+            jets: game.jets.clone(),
+            rounds,
+        });
+    }
+
+    smaller
+}
+
+// simulate plays the game the way the puzzle describes it, one rock and one jet at a time
+fn simulate(rounds: i64, jets: &[char]) -> i64 {
+    let shapes: [&[(i64, i64)]; 5] = [
+        &[(0, 0), (1, 0), (2, 0), (3, 0)],
+        &[(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
+        &[(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
+        &[(0, 0), (0, 1), (0, 2), (0, 3)],
+        &[(0, 0), (1, 0), (0, 1), (1, 1)],
+    ];
+
+    let mut stopped: HashSet<(i64, i64)> = HashSet::new();
+    let mut height = 0;
+    let mut jet = 0;
+
+    for i in 0..rounds as usize {
+        let shape = shapes[i % 5];
+        let fits = |stopped: &HashSet<(i64, i64)>, x: i64, y: i64| {
+            shape.iter().all(|(sx, sy)| {
+                (0..7).contains(&(x + sx)) && y + sy >= 0 && !stopped.contains(&(x + sx, y + sy))
+            })
+        };
+
+        let (mut x, mut y) = (2, height + 3);
+        loop {
+            let dx = if jets[jet % jets.len()] == '<' { -1 } else { 1 };
+            jet += 1;
+            if fits(&stopped, x + dx, y) {
+                x += dx;
+            }
+
+            if !fits(&stopped, x, y - 1) {
+                break;
+            }
+            y -= 1;
+        }
+
+        for (sx, sy) in shape {
+            stopped.insert((x + sx, y + sy));
+            height = height.max(y + sy + 1);
+        }
+    }
+
+    height
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // every case drops all its rocks one by one, so a debug build only gets through a few
+    #[test]
+    fn cycle_extrapolation_matches_simulation() {
+        let config = Config {
+            cases: 50,
+            seed: 2022,
+            max_size: 30,
+        };
+        assert!(run(&config));
+    }
+}
@@ -1,4 +1,11 @@
-use std::{collections::HashMap, fmt, fs};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt, fs, process,
+};
+
+use advent2022::{fuzz, input_path, prop};
+
+mod check;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Material {
@@ -20,26 +27,41 @@ impl fmt::Display for Material {
 }
 
 fn main() {
+    if let Some(config) = prop::requested() {
+        process::exit(if check::run(&config) { 0 } else { 1 });
+    }
+    fuzz::exit_if_requested("day17", parse_jets);
+
     // let input = fs::read_to_string("input/day17-test.txt").unwrap();
-    let input = fs::read_to_string("input/day17.txt").unwrap();
+    let input = fs::read_to_string(input_path("day17")).unwrap();
+    let jets = parse_jets(&input).unwrap_or_else(|e| panic!("invalid input: {}", e));
 
-    let part1 = run_game(2022, input.clone());
+    let part1 = run_game(2022, &jets);
     println!("Part 1 - {}", part1);
 
-    let part2 = run_game(1000000000000, input.clone());
+    let part2 = run_game(1000000000000, &jets);
     println!("Part 2 - {}", part2);
 }
 
-fn run_game(rounds: i64, input: String) -> i64 {
-    let jets: Vec<char> = input.chars().collect();
+fn parse_jets(input: &str) -> Result<Vec<char>, String> {
+    if input.is_empty() {
+        return Err("no jets".to_string());
+    }
+    match input.chars().position(|ch| ch != '<' && ch != '>') {
+        Some(i) => Err(format!("unexpected character at position {}", i + 1)),
+        None => Ok(input.chars().collect()),
+    }
+}
+
+fn run_game(rounds: i64, jets: &[char]) -> i64 {
     let mut jet_i = 0;
     let mut game: [Vec<Material>; 7] = Default::default();
 
     let mut seen = HashMap::new();
 
     for i in 0..rounds {
-        let dif = differential(&game);
-        if let Some((old_round, old_h)) = seen.get(&(jet_i, i % 5, dif)) {
+        let key = (jet_i, i % 5, top_shape(&game));
+        if let Some((old_round, old_h)) = seen.get(&key) {
             let h_cur = highest(&game, Material::Stopped) + 1;
             let period = i - old_round;
             let per_period = h_cur - old_h;
@@ -54,12 +76,9 @@ fn run_game(rounds: i64, input: String) -> i64 {
             }
             panic!("should be unreachable");
         }
-        seen.insert(
-            (jet_i, i % 5, dif),
-            (i, highest(&game, Material::Stopped) + 1),
-        );
+        seen.insert(key, (i, highest(&game, Material::Stopped) + 1));
 
-        draw_shape(&mut game, i as i64);
+        draw_shape(&mut game, i);
 
         loop {
             apply_jet(&mut game, jets[jet_i]);
@@ -77,43 +96,45 @@ fn run_game(rounds: i64, input: String) -> i64 {
     highest(&game, Material::Stopped) + 1
 }
 
-fn differential(game: &[Vec<Material>; 7]) -> [i64; 5] {
-    let mut h_vals = Vec::new();
-
-    for col in game {
-        let mut i = col.len();
-        while i > 0 {
-            i -= 1;
-            if col[i] == Material::Stopped {
-                break;
-            }
+// top_shape returns the empty cells that falling rocks can still reach, relative to the highest rock.
+// Rocks only move sideways and down, so together with the jet and rock index this is the whole
+// state of the game. Comparing just the column heights can report cycles that aren't there.
+fn top_shape(game: &[Vec<Material>; 7]) -> Vec<(i64, i64)> {
+    let top = highest(game, Material::Stopped);
+    let stopped = |x: i64, y: i64| game[x as usize].get(y as usize) == Some(&Material::Stopped);
+
+    let mut reachable = HashSet::new();
+    let mut queue: Vec<(i64, i64)> = (0..7).map(|x| (x, top + 1)).collect();
+    while let Some((x, y)) = queue.pop() {
+        // like freeze, don't look more than 100 rows down, open shafts would never repeat
+        if !(0..7).contains(&x)
+            || y < 0
+            || top - y > 100
+            || stopped(x, y)
+            || !reachable.insert((x, y))
+        {
+            continue;
         }
-        h_vals.push(i)
-    }
 
-    let min_v = h_vals.iter().min().unwrap().clone();
-    for v in h_vals.iter_mut() {
-        *v -= min_v;
+        queue.push((x - 1, y));
+        queue.push((x + 1, y));
+        queue.push((x, y - 1));
     }
 
-    [
-        h_vals[0] as i64,
-        h_vals[1] as i64,
-        h_vals[2] as i64,
-        h_vals[3] as i64,
-        h_vals[4] as i64,
-    ]
+    let mut shape: Vec<(i64, i64)> = reachable.into_iter().map(|(x, y)| (x, top - y)).collect();
+    shape.sort_unstable();
+    shape
 }
 
 fn freeze(game: &mut [Vec<Material>; 7]) {
     let y_start = highest(game, Material::Falling) as usize + 1;
     let mut y = y_start;
     while y > 0 && y_start - y <= 100 {
-        y = y - 1;
-        for x in 0..7 {
-            if let Some(m) = game[x].get(y) {
+        y -= 1;
+        for column in game.iter_mut() {
+            if let Some(m) = column.get_mut(y) {
                 if *m == Material::Falling {
-                    game[x][y] = Material::Stopped;
+                    *m = Material::Stopped;
                 }
             }
         }
@@ -123,14 +144,14 @@ fn freeze(game: &mut [Vec<Material>; 7]) {
 fn move_down(game: &mut [Vec<Material>; 7]) -> bool {
     let y_start = highest(game, Material::Falling);
     for y in (y_start - 3)..(y_start + 1) {
-        for x in 0..7 {
-            if let Some(v) = game[x].get(y as usize) {
+        for column in game.iter() {
+            if let Some(v) = column.get(y as usize) {
                 if *v != Material::Falling {
                     continue;
                 }
 
                 let y_dest = y - 1;
-                if y_dest == -1 || game[x][y_dest as usize] == Material::Stopped {
+                if y_dest == -1 || column[y_dest as usize] == Material::Stopped {
                     return true;
                 }
             }
@@ -173,7 +194,7 @@ fn apply_jet(game: &mut [Vec<Material>; 7], jet: char) {
                 continue;
             }
             let dest_x = x + increment;
-            if dest_x > 6 || dest_x < 0 {
+            if !(0..=6).contains(&dest_x) {
                 return;
             }
             if let Some(m) = game[dest_x as usize].get(y as usize) {
@@ -220,7 +241,7 @@ fn highest(game: &[Vec<Material>; 7], mat: Material) -> i64 {
     for col in game {
         let mut i = col.len();
         while i > 0 && col.len() - i <= 100 {
-            i = i - 1;
+            i -= 1;
             if col[i] == mat {
                 if i as i64 > highest {
                     highest = i as i64;
@@ -0,0 +1,333 @@
+use std::{
+    env, fs, panic,
+    path::{Path, PathBuf},
+    process,
+    sync::{Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use crate::{prop::catch_panic, rng::Rng};
+
+// A small mutation fuzzer for the puzzle parsers, no network or extra tooling needed.
+// It starts from the real and test inputs in input/, mutates them at random and feeds
+// the result to a parser. A parser may reject anything it likes, but it must not panic or hang.
+// Every crash is minimized and saved under fuzz/regressions/<day>/, and those files are
+// replayed before every fuzz run, so a fixed crash stays fixed:
+//   cargo run --release --bin day13 -- fuzz          replay regressions, then fuzz
+//   cargo run --release --bin day13 -- fuzz 0        only replay regressions
+// `cargo test` replays the regressions of every day the same way.
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub runs: usize,
+    pub seed: u64,
+    pub timeout: Duration,
+}
+
+// requested returns a config when a day was started as `dayXX fuzz [runs] [seed]`
+pub fn requested() -> Option<Config> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first()? != "fuzz" {
+        return None;
+    }
+
+    match Config::from_args(&args[1..]) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    }
+}
+
+// exit_if_requested fuzzes the parser of a day started as `dayXX fuzz [runs] [seed]` and exits,
+// with status 1 when some input made it panic. Otherwise it returns and the day runs as usual
+pub fn exit_if_requested<T>(day: &str, parse: impl Fn(&str) -> Result<T, String>) {
+    if let Some(config) = requested() {
+        process::exit(if run(day, &config, parse) { 0 } else { 1 });
+    }
+}
+
+impl Config {
+    // from_args reads `[runs] [seed]` from the arguments following a `fuzz` subcommand
+    fn from_args(args: &[String]) -> Result<Config, String> {
+        let mut config = Config {
+            runs: 20_000,
+            seed: 0,
+            timeout: Duration::from_secs(5),
+        };
+
+        if let Some(runs) = args.first() {
+            config.runs = runs
+                .parse()
+                .map_err(|_| format!("invalid number of runs `{}`", runs))?;
+        }
+        if let Some(seed) = args.get(1) {
+            config.seed = seed
+                .parse()
+                .map_err(|_| format!("invalid seed `{}`", seed))?;
+        }
+
+        Ok(config)
+    }
+}
+
+// run replays the saved regressions of a day and then fuzzes its parser,
+// returning whether no input made it panic
+pub fn run<T>(day: &str, config: &Config, parse: impl Fn(&str) -> Result<T, String>) -> bool {
+    let target = |input: &[u8]| catch_panic(|| parse(&String::from_utf8_lossy(input))).err();
+
+    // the watchdog can't stop a parser that is stuck, but it can save the input and bail out
+    let current: Current = Arc::new(Mutex::new(None));
+    watchdog(day, config.timeout, current.clone());
+    let run_one = |input: &[u8]| {
+        *current.lock().unwrap() = Some((Instant::now(), input.to_vec()));
+        let crash = target(input);
+        *current.lock().unwrap() = None;
+        crash
+    };
+
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let replayed = replay(day, &run_one);
+    let fuzzed = config.runs == 0 || fuzz(day, config, &run_one);
+
+    panic::set_hook(hook);
+
+    replayed && fuzzed
+}
+
+fn regression_dir(day: &str) -> PathBuf {
+    Path::new("fuzz").join("regressions").join(day)
+}
+
+fn replay(day: &str, run_one: &impl Fn(&[u8]) -> Option<String>) -> bool {
+    let mut files: Vec<PathBuf> = match fs::read_dir(regression_dir(day)) {
+        Ok(entries) => entries.filter_map(|e| e.ok()).map(|e| e.path()).collect(),
+        Err(_) => Vec::new(),
+    };
+    files.sort();
+
+    let mut ok = true;
+    for file in &files {
+        let input = fs::read(file).expect("unable to read regression file");
+        if let Some(reason) = run_one(&input) {
+            println!("FAIL {} regression {}", day, file.display());
+            println!("     {}", reason);
+            ok = false;
+        }
+    }
+
+    if ok {
+        println!("ok   {} regressions ({} inputs)", day, files.len());
+    }
+    ok
+}
+
+fn fuzz(day: &str, config: &Config, run_one: &impl Fn(&[u8]) -> Option<String>) -> bool {
+    let mut corpus = seed_corpus(day);
+    if corpus.is_empty() {
+        corpus.push(Vec::new());
+    }
+    let seeds = corpus.len();
+    let max_len = 2 * corpus.iter().map(|c| c.len()).max().unwrap_or(0) + 64;
+
+    let mut rng = Rng::new(config.seed);
+    for i in 0..config.runs {
+        let input = mutate(&mut rng, &corpus, max_len);
+
+        match run_one(&input) {
+            Some(reason) => {
+                let (input, reason) = minimize(input, reason, run_one);
+                let file = save(day, "crash", &input);
+                println!("FAIL {} fuzz (run {}, seed {})", day, i, config.seed);
+                println!("     {}", reason);
+                println!("     input saved to {}", file.display());
+                return false;
+            }
+            // inputs that still parse are the interesting ones to keep mutating,
+            // the real inputs stay in the corpus so it doesn't drift too far
+            None => {
+                if corpus.len() < seeds + 200 {
+                    corpus.push(input);
+                } else if rng.chance(0.1) {
+                    let i = seeds + rng.below(corpus.len() - seeds);
+                    corpus[i] = input;
+                }
+            }
+        }
+    }
+
+    println!("ok   {} fuzz ({} runs)", day, config.runs);
+    true
+}
+
+// seed_corpus reads every input file of a day, like input/day13.txt and input/day13-test.txt
+fn seed_corpus(day: &str) -> Vec<Vec<u8>> {
+    let mut files: Vec<PathBuf> = match fs::read_dir("input") {
+        Ok(entries) => entries
+            .filter_map(|e| e.ok())
+            .map(|e| e.path())
+            .filter(|p| {
+                p.file_name()
+                    .and_then(|n| n.to_str())
+                    .is_some_and(|n| n.starts_with(day) && n.ends_with(".txt"))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    files.sort();
+
+    files.iter().filter_map(|f| fs::read(f).ok()).collect()
+}
+
+// bytes that show up a lot in puzzle inputs, plus a few that tend to confuse parsers
+const INTERESTING: &[u8] = b"0123456789-+,.:;=[]<>$/ \n\n\t\rabcxyzABCXYZ\x00\xff";
+
+const NUMBERS: &[&str] = &[
+    "0",
+    "1",
+    "-1",
+    "9",
+    "10",
+    "255",
+    "256",
+    "65536",
+    "2147483648",
+    "9223372036854775807",
+    "-9223372036854775808",
+    "99999999999999999999",
+];
+
+// mutate picks an input from the corpus and applies a few random edits to it
+fn mutate(rng: &mut Rng, corpus: &[Vec<u8>], max_len: usize) -> Vec<u8> {
+    let mut input = rng.pick(corpus).clone();
+
+    for _ in 0..rng.range(1, 4) {
+        let len = input.len() as i64;
+        let at = rng.range(0, len) as usize;
+        let to = rng.range(at as i64, len.min(at as i64 + 32)) as usize;
+
+        match rng.below(8) {
+            // overwrite or insert a single byte
+            0 if at < input.len() => input[at] = *rng.pick(INTERESTING),
+            1 => input.insert(at, *rng.pick(INTERESTING)),
+            // drop a few bytes, or everything from some point on
+            2 => {
+                input.drain(at..to);
+            }
+            3 => input.truncate(at),
+            // repeat a chunk, which makes long lines and deep nesting
+            4 => {
+                let chunk = input[at..to].to_vec();
+                for _ in 0..rng.range(1, 8) {
+                    input.splice(at..at, chunk.iter().cloned());
+                }
+            }
+            // splice in a piece of another input
+            5 => {
+                let other = rng.pick(corpus);
+                let from = rng.range(0, other.len() as i64) as usize;
+                let until = rng.range(from as i64, other.len().min(from + 64) as i64) as usize;
+                input.splice(at..at, other[from..until].iter().cloned());
+            }
+            // replace a number with one at the edge of what fits
+            6 => {
+                let start = (at..input.len()).find(|i| input[*i].is_ascii_digit());
+                if let Some(start) = start {
+                    let end = (start..input.len())
+                        .find(|i| !input[*i].is_ascii_digit())
+                        .unwrap_or(input.len());
+                    let number = rng.pick(NUMBERS).as_bytes().to_vec();
+                    input.splice(start..end, number);
+                }
+            }
+            // drop or duplicate a whole line
+            _ => {
+                let start = input[..at]
+                    .iter()
+                    .rposition(|b| *b == b'\n')
+                    .map_or(0, |i| i + 1);
+                let end = input[at..]
+                    .iter()
+                    .position(|b| *b == b'\n')
+                    .map_or(input.len(), |i| at + i + 1);
+                if rng.chance(0.5) {
+                    input.drain(start..end);
+                } else {
+                    let line = input[start..end].to_vec();
+                    input.splice(end..end, line);
+                }
+            }
+        }
+    }
+
+    input.truncate(max_len);
+    input
+}
+
+// minimize keeps removing chunks and lines from a crashing input for as long as it keeps crashing
+fn minimize(
+    mut input: Vec<u8>,
+    mut reason: String,
+    run_one: &impl Fn(&[u8]) -> Option<String>,
+) -> (Vec<u8>, String) {
+    let mut chunk = input.len() / 2;
+    while chunk > 0 {
+        let mut start = 0;
+        while start < input.len() {
+            let mut smaller = input[..start].to_vec();
+            smaller.extend_from_slice(&input[(start + chunk).min(input.len())..]);
+            match run_one(&smaller) {
+                Some(r) => {
+                    input = smaller;
+                    reason = r;
+                }
+                None => start += chunk,
+            }
+        }
+        chunk /= 2;
+    }
+
+    (input, reason)
+}
+
+// save writes an input to the regression directory, named after its contents
+fn save(day: &str, kind: &str, input: &[u8]) -> PathBuf {
+    let dir = regression_dir(day);
+    let file = dir.join(format!("{}-{:016x}.txt", kind, fnv1a(input)));
+    fs::create_dir_all(&dir).expect("unable to create regression directory");
+    fs::write(&file, input).expect("unable to write regression file");
+    file
+}
+
+// fnv1a hashes the bytes with 64 bit FNV-1a, which unlike the std hasher
+// gives the same file name for the same input on every Rust version
+fn fnv1a(input: &[u8]) -> u64 {
+    input.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+// Current is the input the parser is working on, and since when
+type Current = Arc<Mutex<Option<(Instant, Vec<u8>)>>>;
+
+fn watchdog(day: &str, timeout: Duration, current: Current) {
+    let day = day.to_string();
+    thread::spawn(move || loop {
+        thread::sleep(Duration::from_millis(100));
+
+        let stuck = match &*current.lock().unwrap() {
+            Some((started, input)) if started.elapsed() > timeout => Some(input.clone()),
+            _ => None,
+        };
+        if let Some(input) = stuck {
+            let file = save(&day, "hang", &input);
+            println!("FAIL {} parser ran for more than {:?}", day, timeout);
+            println!("     input saved to {}", file.display());
+            process::exit(1);
+        }
+    });
+}
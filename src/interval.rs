@@ -0,0 +1,202 @@
+// Closed integer ranges like the section assignments of day 4, where (2, 4) is 2, 3 and 4.
+// IntervalSet keeps a set of integers as sorted ranges that neither overlap nor touch,
+// so equal sets always have equal ranges and the set operations are single merges.
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct IntervalSet {
+    ranges: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet::default()
+    }
+
+    // from_ranges accepts ranges in any order, overlapping or not, and skips backwards ones
+    pub fn from_ranges(ranges: impl IntoIterator<Item = (i64, i64)>) -> IntervalSet {
+        let mut sorted: Vec<(i64, i64)> = ranges.into_iter().filter(|r| r.0 <= r.1).collect();
+        sorted.sort_unstable();
+
+        let mut merged: Vec<(i64, i64)> = Vec::with_capacity(sorted.len());
+        for (start, end) in sorted {
+            match merged.last_mut() {
+                // touching ranges merge too, (1, 2) and (3, 4) are (1, 4)
+                Some(last) if start <= last.1.saturating_add(1) => last.1 = last.1.max(end),
+                _ => merged.push((start, end)),
+            }
+        }
+
+        IntervalSet { ranges: merged }
+    }
+
+    pub fn ranges(&self) -> &[(i64, i64)] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    // len counts the integers in the set, which can be more than fits in an i64
+    pub fn len(&self) -> u64 {
+        self.ranges
+            .iter()
+            .map(|(start, end)| end.abs_diff(*start) + 1)
+            .sum()
+    }
+
+    pub fn contains(&self, x: i64) -> bool {
+        let i = self.ranges.partition_point(|(_, end)| *end < x);
+        self.ranges.get(i).is_some_and(|(start, _)| *start <= x)
+    }
+
+    // covers checks that every integer of the other set is in this one
+    pub fn covers(&self, other: &IntervalSet) -> bool {
+        other.difference(self).is_empty()
+    }
+
+    pub fn insert(&mut self, range: (i64, i64)) {
+        *self = self.union(&IntervalSet::from_ranges([range]));
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        IntervalSet::from_ranges(self.ranges.iter().chain(&other.ranges).cloned())
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            let (start, end) = (a.0.max(b.0), a.1.min(b.1));
+            if start <= end {
+                ranges.push((start, end));
+            }
+            // the range that ends first can't overlap anything further on
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut ranges = Vec::new();
+        let mut j = 0;
+        for &(start, end) in &self.ranges {
+            // skip the cuts that end before this range, they end before the next ones too
+            while j < other.ranges.len() && other.ranges[j].1 < start {
+                j += 1;
+            }
+
+            let mut from = start;
+            let mut k = j;
+            loop {
+                match other.ranges.get(k) {
+                    Some(&(cut_start, cut_end)) if cut_start <= end => {
+                        if cut_start > from {
+                            ranges.push((from, cut_start - 1));
+                        }
+                        if cut_end >= end {
+                            break;
+                        }
+                        from = cut_end + 1;
+                        k += 1;
+                    }
+                    _ => {
+                        ranges.push((from, end));
+                        break;
+                    }
+                }
+            }
+        }
+        IntervalSet { ranges }
+    }
+
+    // gaps are the integers of a range that are not in the set
+    pub fn gaps(&self, within: (i64, i64)) -> IntervalSet {
+        IntervalSet::from_ranges([within]).difference(self)
+    }
+}
+
+// IntervalTree finds every range that contains a point in O(log n + k).
+// The ranges are sorted by start and seen as a balanced binary tree, where the middle
+// of every slice is the root of that slice, and every node knows the highest end below it.
+#[derive(Debug, Clone)]
+pub struct IntervalTree<T> {
+    nodes: Vec<((i64, i64), T)>,
+    max_end: Vec<i64>,
+}
+
+impl<T> IntervalTree<T> {
+    pub fn new(mut nodes: Vec<((i64, i64), T)>) -> IntervalTree<T> {
+        nodes.sort_by_key(|(range, _)| *range);
+        let mut max_end = vec![i64::MIN; nodes.len()];
+        fill_max_end(&nodes, &mut max_end, 0, nodes.len());
+        IntervalTree { nodes, max_end }
+    }
+
+    // stab returns the values of all ranges that contain x, ordered by range
+    pub fn stab(&self, x: i64) -> Vec<&T> {
+        let mut found = Vec::new();
+        self.visit(x, 0, self.nodes.len(), &mut found);
+        found
+    }
+
+    fn visit<'a>(&'a self, x: i64, lo: usize, hi: usize, found: &mut Vec<&'a T>) {
+        if lo >= hi {
+            return;
+        }
+        let mid = (lo + hi) / 2;
+        if self.max_end[mid] < x {
+            return;
+        }
+
+        self.visit(x, lo, mid, found);
+        let ((start, end), value) = &self.nodes[mid];
+        // everything to the right starts even later
+        if *start <= x {
+            if x <= *end {
+                found.push(value);
+            }
+            self.visit(x, mid + 1, hi, found);
+        }
+    }
+}
+
+fn fill_max_end<T>(nodes: &[((i64, i64), T)], max_end: &mut [i64], lo: usize, hi: usize) -> i64 {
+    if lo >= hi {
+        return i64::MIN;
+    }
+    let mid = (lo + hi) / 2;
+    let left = fill_max_end(nodes, max_end, lo, mid);
+    let right = fill_max_end(nodes, max_end, mid + 1, hi);
+    max_end[mid] = nodes[mid].0 .1.max(left).max(right);
+    max_end[mid]
+}
+
+// max_overlap finds the most ranges that share an integer, and the first integer where
+// that happens, by sweeping over the starts and ends
+pub fn max_overlap(ranges: &[(i64, i64)]) -> Option<(usize, i64)> {
+    // an end counts just past the range, and at the same point ends go before starts
+    let mut events: Vec<(i64, i64)> = Vec::with_capacity(ranges.len() * 2);
+    for &(start, end) in ranges.iter().filter(|r| r.0 <= r.1) {
+        events.push((start, 1));
+        if let Some(after) = end.checked_add(1) {
+            events.push((after, -1));
+        }
+    }
+    events.sort_unstable_by_key(|(at, change)| (*at, *change));
+
+    let mut depth = 0i64;
+    let mut best: Option<(usize, i64)> = None;
+    for (at, change) in events {
+        depth += change;
+        if best.is_none_or(|(most, _)| depth as usize > most) && depth > 0 {
+            best = Some((depth as usize, at));
+        }
+    }
+    best
+}
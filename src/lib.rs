@@ -0,0 +1,14 @@
+use std::env;
+
+pub mod fuzz;
+pub mod interval;
+pub mod prompt;
+pub mod prop;
+pub mod rng;
+
+// input_path returns the path of a day's puzzle input.
+// Setting AOC_INPUT overrides it, so solvers can be run on generated inputs:
+//   AOC_INPUT=/tmp/day07.txt cargo run --release --bin day07
+pub fn input_path(day: &str) -> String {
+    env::var("AOC_INPUT").unwrap_or_else(|_| format!("input/{}.txt", day))
+}
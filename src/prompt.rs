@@ -0,0 +1,149 @@
+use std::{env, fs, path::Path, str::FromStr};
+
+use rustyline::{
+    completion::Completer, error::ReadlineError, highlight::Highlighter, hint::Hinter,
+    history::DefaultHistory, validate::Validator, Context, Editor, Helper,
+};
+
+// A prompt for poking at a day's parsed input.
+// A day passes its parsed input as the state and a list of commands that query it,
+// `help` and `quit` come for free. History is kept per day in target/repl/,
+// and tab completes command names and, where a command supports it, its arguments:
+//   cargo run --release --bin aoc -- repl day07
+
+pub struct Command<S: ?Sized> {
+    pub name: &'static str,
+    pub args: &'static str,
+    pub help: &'static str,
+    pub run: fn(&S, &[&str]) -> Result<String, String>,
+    // complete lists every possible argument, the prompt filters them by what was typed
+    pub complete: Option<fn(&S) -> Vec<String>>,
+}
+
+// requested tells whether a day was started as `dayXX repl`
+pub fn requested() -> bool {
+    env::args().nth(1).is_some_and(|arg| arg == "repl")
+}
+
+// run reads commands until `quit` or end of input
+pub fn run<S: ?Sized>(day: &str, state: &S, commands: &[Command<S>]) {
+    let mut editor: Editor<Prompt<S>, DefaultHistory> =
+        Editor::new().expect("unable to start the prompt");
+    editor.set_helper(Some(Prompt { state, commands }));
+
+    let history = Path::new("target")
+        .join("repl")
+        .join(format!("{}.history", day));
+    let _ = editor.load_history(&history);
+
+    println!("{} loaded, type `help` for a list of commands", day);
+    loop {
+        let line = match editor.readline(&format!("{}> ", day)) {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(_) => break,
+        };
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some(name) = words.first() else {
+            continue;
+        };
+        let _ = editor.add_history_entry(line.as_str());
+
+        match *name {
+            "quit" | "exit" => break,
+            "help" => print_help(commands),
+            _ => match commands.iter().find(|c| c.name == *name) {
+                Some(command) => match (command.run)(state, &words[1..]) {
+                    Ok(output) => println!("{}", output),
+                    Err(e) => println!("error: {}", e),
+                },
+                None => println!("unknown command `{}`, try `help`", name),
+            },
+        }
+    }
+
+    if let Some(dir) = history.parent() {
+        let _ = fs::create_dir_all(dir);
+    }
+    let _ = editor.save_history(&history);
+}
+
+fn print_help<S: ?Sized>(commands: &[Command<S>]) {
+    let mut lines: Vec<(String, &str)> = commands
+        .iter()
+        .map(|c| (format!("{} {}", c.name, c.args).trim().to_string(), c.help))
+        .collect();
+    lines.push(("help".to_string(), "show this list"));
+    lines.push(("quit".to_string(), "leave"));
+
+    let width = lines
+        .iter()
+        .map(|(usage, _)| usage.len())
+        .max()
+        .unwrap_or(0);
+    for (usage, help) in lines {
+        println!("  {:width$}  {}", usage, help, width = width);
+    }
+}
+
+// parse_arg parses a command argument, naming it in the error
+pub fn parse_arg<T: FromStr>(args: &[&str], i: usize, name: &str) -> Result<T, String> {
+    let arg = args.get(i).ok_or_else(|| format!("missing <{}>", name))?;
+    arg.parse()
+        .map_err(|_| format!("invalid <{}> `{}`", name, arg))
+}
+
+struct Prompt<'a, S: ?Sized> {
+    state: &'a S,
+    commands: &'a [Command<S>],
+}
+
+impl<S: ?Sized> Completer for Prompt<'_, S> {
+    type Candidate = String;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<String>)> {
+        let line = &line[..pos];
+        let start = line.rfind(' ').map_or(0, |i| i + 1);
+        let word = &line[start..];
+
+        let options: Vec<String> = if start == 0 {
+            self.commands
+                .iter()
+                .map(|c| c.name.to_string())
+                .chain(["help".to_string(), "quit".to_string()])
+                .collect()
+        } else {
+            let name = line.split_whitespace().next().unwrap_or_default();
+            match self.commands.iter().find(|c| c.name == name) {
+                Some(Command {
+                    complete: Some(complete),
+                    ..
+                }) => complete(self.state),
+                _ => Vec::new(),
+            }
+        };
+
+        let mut matches: Vec<String> = options
+            .into_iter()
+            .filter(|o| o.starts_with(word))
+            .collect();
+        matches.sort();
+        matches.dedup();
+        Ok((start, matches))
+    }
+}
+
+impl<S: ?Sized> Hinter for Prompt<'_, S> {
+    type Hint = String;
+}
+
+impl<S: ?Sized> Highlighter for Prompt<'_, S> {}
+
+impl<S: ?Sized> Validator for Prompt<'_, S> {}
+
+impl<S: ?Sized> Helper for Prompt<'_, S> {}
@@ -0,0 +1,279 @@
+use std::{
+    env,
+    fmt::Debug,
+    panic::{self, AssertUnwindSafe},
+    process,
+};
+
+use crate::rng::Rng;
+
+// A tiny property testing harness.
+// A property is checked against random inputs of growing size,
+// and when it fails the input is shrunk to the smallest one that still fails.
+// Panics inside the property count as failures too.
+
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub cases: usize,
+    pub seed: u64,
+    pub max_size: usize,
+}
+
+// requested returns a config when a day was started as `dayXX check [cases] [seed]`
+pub fn requested() -> Option<Config> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    if args.first()? != "check" {
+        return None;
+    }
+
+    match Config::from_args(&args[1..]) {
+        Ok(config) => Some(config),
+        Err(e) => {
+            eprintln!("{}", e);
+            process::exit(2);
+        }
+    }
+}
+
+impl Config {
+    // from_args reads `[cases] [seed]` from the arguments following a `check` subcommand
+    fn from_args(args: &[String]) -> Result<Config, String> {
+        let mut config = Config {
+            cases: 200,
+            seed: 0,
+            max_size: 30,
+        };
+
+        if let Some(cases) = args.first() {
+            config.cases = cases
+                .parse()
+                .map_err(|_| format!("invalid number of cases `{}`", cases))?;
+        }
+        if let Some(seed) = args.get(1) {
+            config.seed = seed
+                .parse()
+                .map_err(|_| format!("invalid seed `{}`", seed))?;
+        }
+
+        Ok(config)
+    }
+}
+
+// check runs a property and prints the outcome, returning whether it held.
+// generate gets a size hint that grows from 1 to config.max_size over the cases.
+pub fn check<T, G, S, P>(name: &str, config: &Config, generate: G, shrink: S, property: P) -> bool
+where
+    T: Debug,
+    G: Fn(&mut Rng, usize) -> T,
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let mut rng = Rng::new(config.seed);
+
+    // keep panic messages from flooding the output while we go through cases
+    let hook = panic::take_hook();
+    panic::set_hook(Box::new(|_| {}));
+
+    let mut failure = None;
+    for case in 0..config.cases {
+        let size = 1 + case * config.max_size / config.cases.max(1);
+        let input = generate(&mut rng, size);
+        if let Err(reason) = run(&property, &input) {
+            failure = Some((case, shrink_failure(input, reason, &shrink, &property)));
+            break;
+        }
+    }
+
+    panic::set_hook(hook);
+
+    match failure {
+        None => {
+            println!("ok   {} ({} cases)", name, config.cases);
+            true
+        }
+        Some((case, (input, reason, steps))) => {
+            println!(
+                "FAIL {} (case {}, seed {}, shrunk {} times)",
+                name, case, config.seed, steps
+            );
+            println!("     input: {:?}", input);
+            println!("     {}", reason);
+            false
+        }
+    }
+}
+
+fn run<T, P>(property: &P, input: &T) -> Result<(), String>
+where
+    P: Fn(&T) -> Result<(), String>,
+{
+    catch_panic(|| property(input)).and_then(|result| result)
+}
+
+// catch_panic runs f and turns a panic into an error with the panic message
+pub(crate) fn catch_panic<R>(f: impl FnOnce() -> R) -> Result<R, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        format!("panicked: {}", message)
+    })
+}
+
+// shrink_failure greedily takes the first smaller input that still fails, until none do
+fn shrink_failure<T, S, P>(
+    mut input: T,
+    mut reason: String,
+    shrink: &S,
+    property: &P,
+) -> (T, String, usize)
+where
+    S: Fn(&T) -> Vec<T>,
+    P: Fn(&T) -> Result<(), String>,
+{
+    let mut steps = 0;
+    'outer: while steps < 1000 {
+        for candidate in shrink(&input) {
+            if let Err(r) = run(property, &candidate) {
+                input = candidate;
+                reason = r;
+                steps += 1;
+                continue 'outer;
+            }
+        }
+        break;
+    }
+
+    (input, reason, steps)
+}
+
+// shrink_int gives values between target and x, closest to target first
+pub fn shrink_int(x: i64, target: i64) -> Vec<i64> {
+    let mut candidates = Vec::new();
+    let mut diff = x - target;
+    while diff != 0 {
+        candidates.push(x - diff);
+        diff /= 2;
+    }
+    candidates
+}
+
+// shrink_vec removes chunks and single elements, and then tries to shrink each element
+pub fn shrink_vec<T: Clone>(v: &[T], shrink_elem: impl Fn(&T) -> Vec<T>) -> Vec<Vec<T>> {
+    let mut candidates = Vec::new();
+
+    let mut chunk = v.len() / 2;
+    while chunk > 0 {
+        for start in (0..v.len()).step_by(chunk) {
+            let mut smaller = v[..start].to_vec();
+            smaller.extend_from_slice(&v[(start + chunk).min(v.len())..]);
+            candidates.push(smaller);
+        }
+        chunk /= 2;
+    }
+
+    for (i, elem) in v.iter().enumerate() {
+        for shrunk in shrink_elem(elem) {
+            let mut smaller = v.to_vec();
+            smaller[i] = shrunk;
+            candidates.push(smaller);
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: Config = Config {
+        cases: 100,
+        seed: 2022,
+        max_size: 30,
+    };
+
+    fn numbers(rng: &mut Rng, size: usize) -> Vec<i64> {
+        (0..size).map(|_| rng.range(0, 100)).collect()
+    }
+
+    fn shrink_numbers(v: &[i64]) -> Vec<Vec<i64>> {
+        shrink_vec(v, |x| shrink_int(*x, 0))
+    }
+
+    #[test]
+    fn holding_property_passes() {
+        let reversed_twice = |v: &Vec<i64>| {
+            let mut twice = v.clone();
+            twice.reverse();
+            twice.reverse();
+            match twice == *v {
+                true => Ok(()),
+                false => Err("reversing twice changed the numbers".to_string()),
+            }
+        };
+        assert!(check(
+            "reversing twice changes nothing",
+            &CONFIG,
+            numbers,
+            |v| shrink_numbers(v),
+            reversed_twice
+        ));
+    }
+
+    #[test]
+    fn failing_property_fails() {
+        let small_sum = |v: &Vec<i64>| match v.iter().sum::<i64>() < 100 {
+            true => Ok(()),
+            false => Err("sum too big".to_string()),
+        };
+        assert!(!check(
+            "sums stay small",
+            &CONFIG,
+            numbers,
+            |v| shrink_numbers(v),
+            small_sum
+        ));
+    }
+
+    #[test]
+    fn failures_shrink_to_the_smallest_input() {
+        let small_sum = |v: &Vec<i64>| match v.iter().sum::<i64>() < 100 {
+            true => Ok(()),
+            false => Err("sum too big".to_string()),
+        };
+        let (input, reason, _) = shrink_failure(
+            vec![70, 3, 80, 12],
+            "sum too big".to_string(),
+            &|v: &Vec<i64>| shrink_numbers(v),
+            &small_sum,
+        );
+        assert_eq!(input.iter().sum::<i64>(), 100);
+        assert_eq!(reason, "sum too big");
+    }
+
+    #[test]
+    fn panics_count_as_failures() {
+        let divide = |v: &Vec<i64>| {
+            let _: i64 = v.iter().map(|x| 100 / x).sum();
+            Ok(())
+        };
+        let (input, reason, _) = shrink_failure(
+            vec![5, 0, 7],
+            String::new(),
+            &|v: &Vec<i64>| shrink_numbers(v),
+            &divide,
+        );
+        assert_eq!(input, vec![0]);
+        assert!(reason.starts_with("panicked"), "{}", reason);
+    }
+
+    #[test]
+    fn shrink_int_moves_towards_the_target() {
+        assert_eq!(shrink_int(10, 0), vec![0, 5, 8, 9]);
+        assert_eq!(shrink_int(-4, 1), vec![1, -2, -3]);
+        assert!(shrink_int(3, 3).is_empty());
+    }
+}
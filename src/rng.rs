@@ -0,0 +1,54 @@
+// Rng is a tiny seeded pseudo random number generator (xorshift64*).
+// It is not remotely cryptographic, but it is reproducible from a seed,
+// which is all we need for generating puzzle inputs.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Rng {
+        // run the seed through splitmix64 so that small seeds like 0 and 1
+        // still give well mixed (and never zero) states
+        let mut z = seed.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^= z >> 31;
+
+        Rng { state: z.max(1) }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state ^= self.state >> 12;
+        self.state ^= self.state << 25;
+        self.state ^= self.state >> 27;
+        self.state.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    // below returns a value in 0..n
+    pub fn below(&mut self, n: usize) -> usize {
+        assert!(n > 0, "cannot pick below 0");
+        (self.next_u64() % n as u64) as usize
+    }
+
+    // range returns a value in lo..=hi
+    pub fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        assert!(lo <= hi, "empty range {}..={}", lo, hi);
+        lo + (self.next_u64() % (hi - lo + 1) as u64) as i64
+    }
+
+    // chance returns true with a probability of p
+    pub fn chance(&mut self, p: f64) -> bool {
+        ((self.next_u64() >> 11) as f64) < p * (1u64 << 53) as f64
+    }
+
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len())]
+    }
+
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            items.swap(i, self.below(i + 1));
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use std::{env, fs, path::PathBuf, process::Command};
+
+// Every input the fuzzer ever saved under fuzz/regressions/<day>/ is replayed through that
+// day's own binary with `dayXX fuzz 0`, so a crash that was fixed once can't come back
+// without `cargo test` noticing.
+
+// bin finds a day's binary, which cargo builds next to the directory of the test binary
+fn bin(day: &str) -> PathBuf {
+    let mut path = env::current_exe().expect("unable to find the test binary");
+    path.pop();
+    if path.ends_with("deps") {
+        path.pop();
+    }
+    path.join(format!("{}{}", day, env::consts::EXE_SUFFIX))
+}
+
+#[test]
+fn regressions_stay_fixed() {
+    let mut days: Vec<(String, usize)> = fs::read_dir("fuzz/regressions")
+        .expect("unable to read fuzz/regressions")
+        .filter_map(|e| e.ok())
+        .filter(|e| e.path().is_dir())
+        .map(|e| {
+            let inputs = fs::read_dir(e.path()).map_or(0, |files| files.count());
+            (e.file_name().to_string_lossy().to_string(), inputs)
+        })
+        .collect();
+    days.sort();
+    assert!(!days.is_empty(), "no regressions to replay");
+
+    for (day, inputs) in days {
+        let output = Command::new(bin(&day))
+            .args(["fuzz", "0"])
+            .output()
+            .unwrap_or_else(|e| panic!("unable to run {}: {}", day, e));
+        let stdout = String::from_utf8_lossy(&output.stdout);
+
+        assert!(
+            output.status.success(),
+            "{} regressions failed:\n{}",
+            day,
+            stdout
+        );
+        assert!(
+            stdout.contains(&format!("{} regressions ({} inputs)", day, inputs)),
+            "{} did not replay all {} inputs:\n{}",
+            day,
+            inputs,
+            stdout
+        );
+    }
+}